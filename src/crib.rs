@@ -0,0 +1,62 @@
+use std::convert::Infallible;
+use std::str::FromStr;
+
+use regex::bytes::Regex;
+
+/// Crib, a known part of the plaintext such as the flag format (e.g. `flag{`).
+#[derive(Debug, Clone)]
+pub enum Crib {
+    /// The plaintext contains the given bytes.
+    Substring(Vec<u8>),
+    /// The plaintext matches the given regex.
+    Regex(Regex),
+}
+
+impl Crib {
+    /// Check if the data matches the crib.
+    pub fn is_match(&self, data: &[u8]) -> bool {
+        match self {
+            Crib::Substring(substring) => {
+                substring.is_empty() || data.windows(substring.len()).any(|w| w == substring)
+            }
+            Crib::Regex(regex) => regex.is_match(data),
+        }
+    }
+}
+
+impl FromStr for Crib {
+    type Err = Infallible;
+
+    /// Parse a crib as a regex, or as a substring if it is not a valid regex (e.g. `flag{`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match Regex::new(s) {
+            Ok(regex) => Crib::Regex(regex),
+            Err(_) => Crib::Substring(s.as_bytes().to_vec()),
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substring() {
+        let crib = "flag{".parse::<Crib>().unwrap();
+        assert!(matches!(crib, Crib::Substring(_)));
+        assert!(crib.is_match(b"flag{hello}"));
+        assert!(crib.is_match(b"the flag{is} here"));
+        assert!(!crib.is_match(b"FLAG{hello}"));
+        assert!(!crib.is_match(b"fla"));
+    }
+
+    #[test]
+    fn test_regex() {
+        let crib = r"(?i)(flag|htb)\{.*\}".parse::<Crib>().unwrap();
+        assert!(matches!(crib, Crib::Regex(_)));
+        assert!(crib.is_match(b"HTB{hello}"));
+        assert!(crib.is_match(b"\xff\x00flag{hello}\xfe"));
+        assert!(!crib.is_match(b"flag{hello"));
+    }
+}
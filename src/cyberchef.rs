@@ -0,0 +1,327 @@
+use serde_json::{json, Value};
+
+use crate::{Recipe, RecipeError, RecipeStep};
+
+/// CyberChef operations decoding a base: the recipe step, the operation and its arguments.
+/// The first argument identifies the variant of the base, the first matching entry is used on export.
+fn operations() -> Vec<(String, &'static str, Value)> {
+    let mut operations = vec![
+        ("base2", "From Binary", json!(["None", 8])),
+        ("hex", "From Hex", json!(["Auto"])),
+        ("hex", "From Hex", json!(["None"])),
+        ("hex-escape", "From Hex", json!(["\\x"])),
+        ("base32", "From Base32", json!(["A-Z2-7=", true])),
+        ("base32-nopad", "From Base32", json!(["A-Z2-7=", true])),
+        ("base32hex", "From Base32", json!(["0-9A-V=", true])),
+        ("base45", "From Base45", json!(["0-9A-Z $%*+\\-./:", true])),
+        (
+            "base58",
+            "From Base58",
+            json!([
+                "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+                true
+            ]),
+        ),
+        (
+            "base64",
+            "From Base64",
+            json!(["A-Za-z0-9+/=", true, false]),
+        ),
+        (
+            "base64-nopad",
+            "From Base64",
+            json!(["A-Za-z0-9+/", true, false]),
+        ),
+        (
+            "base64url",
+            "From Base64",
+            json!(["A-Za-z0-9-_=", true, false]),
+        ),
+        (
+            "base64url-nopad",
+            "From Base64",
+            json!(["A-Za-z0-9-_", true, false]),
+        ),
+        (
+            "base85",
+            "From Base85",
+            json!(["0-9A-Za-z!#$%&()*+\\-;<=>?@^_`{|}~", true, ""]),
+        ),
+        ("ascii85", "From Base85", json!(["!-u", true, "z"])),
+        ("base92", "From Base92", json!([])),
+        (
+            "z85",
+            "From Base85",
+            json!(["0-9a-zA-Z.\\-:+=^!/*?&<>()[]{}@%$#", true, ""]),
+        ),
+        ("url", "URL Decode", json!([false])),
+        ("html", "From HTML Entity", json!([])),
+        ("quoted-printable", "From Quoted Printable", json!([])),
+        (
+            "unicode-escape",
+            "Unescape Unicode Characters",
+            json!(["\\u"]),
+        ),
+    ]
+    .into_iter()
+    .map(|(step, op, args)| (step.to_string(), op, args))
+    .collect::<Vec<_>>();
+
+    // The character codes, for each delimiter of CyberChef.
+    for (separator, delimiter) in CHARCODE_DELIMITERS {
+        let step = |name: &str| match separator {
+            "space" => name.to_string(),
+            separator => format!("{}({})", name, separator),
+        };
+        operations.push((
+            step("charcode-dec"),
+            "From Decimal",
+            json!([delimiter, false]),
+        ));
+        operations.push((step("charcode-oct"), "From Octal", json!([delimiter])));
+        operations.push((step("charcode-hex"), "From Hex", json!([delimiter])));
+        operations.push((step("charcode-bin"), "From Binary", json!([delimiter, 8])));
+    }
+    operations.push((
+        "charcode-hex(prefix,none)".to_string(),
+        "From Hex",
+        json!(["0x"]),
+    ));
+    operations.push((
+        "charcode-hex(prefix,comma)".to_string(),
+        "From Hex",
+        json!(["0x with comma"]),
+    ));
+    operations
+}
+
+/// Separators of the character codes and their CyberChef delimiter.
+const CHARCODE_DELIMITERS: [(&str, &str); 6] = [
+    ("space", "Space"),
+    ("comma", "Comma"),
+    ("semicolon", "Semi-colon"),
+    ("colon", "Colon"),
+    ("newline", "Line feed"),
+    ("crlf", "CRLF"),
+];
+
+impl Recipe {
+    /// Import a CyberChef recipe, as exported in JSON by CyberChef.
+    /// Only the operations decoding a supported base are accepted, the disabled ones are ignored.
+    pub fn from_cyberchef(recipe: &str) -> Result<Self, RecipeError> {
+        let invalid = |e: &dyn std::fmt::Display| RecipeError::InvalidCyberChef(e.to_string());
+        let recipe: Value = serde_json::from_str(recipe).map_err(|e| invalid(&e))?;
+        let recipe = recipe
+            .as_array()
+            .ok_or_else(|| invalid(&"expected a list of operations"))?;
+
+        let operations = operations();
+        let mut steps = Vec::new();
+        for operation in recipe {
+            if operation["disabled"] == json!(true) {
+                continue;
+            }
+            let name = operation["op"]
+                .as_str()
+                .ok_or_else(|| invalid(&"expected an operation name"))?;
+            let variant = &operation["args"][0];
+
+            let (step, _, _) = operations
+                .iter()
+                .find(|(_, op, args)| *op == name && (variant.is_null() || args[0] == *variant))
+                .ok_or_else(|| {
+                    RecipeError::UnsupportedOperation(format!("{} {}", name, operation["args"]))
+                })?;
+            steps.push(step.parse()?);
+        }
+
+        if steps.is_empty() {
+            return Err(RecipeError::Empty);
+        }
+        Ok(Self { steps })
+    }
+
+    /// Export the recipe as a CyberChef recipe in JSON, to be loaded in CyberChef.
+    pub fn to_cyberchef_json(&self) -> Result<String, RecipeError> {
+        let operations = self
+            .cyberchef_operations()?
+            .into_iter()
+            .map(|(op, args)| json!({ "op": op, "args": args }))
+            .collect::<Vec<_>>();
+        Ok(Value::Array(operations).to_string())
+    }
+
+    /// Export the recipe as a CyberChef URL fragment, e.g. `recipe=From_Hex('Auto')`.
+    /// It can be appended to the CyberChef URL after a `#`.
+    pub fn to_cyberchef_url(&self) -> Result<String, RecipeError> {
+        let mut operations = String::new();
+        for (op, args) in self.cyberchef_operations()? {
+            let args = args
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|arg| match arg {
+                    Value::String(s) => {
+                        format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"))
+                    }
+                    arg => arg.to_string(),
+                })
+                .collect::<Vec<_>>();
+            operations.push_str(&format!("{}({})", op.replace(' ', "_"), args.join(",")));
+        }
+        Ok(format!("recipe={}", percent_encode(&operations)))
+    }
+
+    /// Get the CyberChef operation and its arguments for each step of the recipe.
+    fn cyberchef_operations(&self) -> Result<Vec<(&'static str, Value)>, RecipeError> {
+        let operations = operations();
+        self.steps
+            .iter()
+            .map(|step| {
+                // The options of the base must match too, e.g. the separator of the character codes.
+                let base = step.base()?;
+                let (name, options) = (base.get_metadata().name, base.options());
+                operations
+                    .iter()
+                    .find(|(variant, _, _)| {
+                        variant
+                            .parse::<RecipeStep>()
+                            .ok()
+                            .and_then(|variant| variant.base().ok())
+                            .is_some_and(|base| {
+                                base.get_metadata().name == name && base.options() == options
+                            })
+                    })
+                    .map(|(_, op, args)| (*op, args.clone()))
+                    .ok_or_else(|| RecipeError::NotInCyberChef(step.to_string()))
+            })
+            .collect()
+    }
+}
+
+/// Percent-encode the characters which are not allowed in a URL fragment.
+fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => (b as char).to_string(),
+            b'-' | b'_' | b'.' | b'~' | b'(' | b')' | b'\'' | b'!' | b'*' | b',' | b'/' | b':' => {
+                (b as char).to_string()
+            }
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export() {
+        let recipe: Recipe = "b64(url)|hex".parse().unwrap();
+        assert_eq!(
+            recipe.to_cyberchef_json().unwrap(),
+            r#"[{"args":["A-Za-z0-9-_=",true,false],"op":"From Base64"},{"args":["Auto"],"op":"From Hex"}]"#
+        );
+        assert_eq!(
+            recipe.to_cyberchef_url().unwrap(),
+            "recipe=From_Base64('A-Za-z0-9-_%3D',true,false)From_Hex('Auto')"
+        );
+
+        let recipe: Recipe = "b32h|base32-nopad".parse().unwrap();
+        assert_eq!(
+            recipe.to_cyberchef_json().unwrap(),
+            r#"[{"args":["0-9A-V=",true],"op":"From Base32"},{"args":["A-Z2-7=",true],"op":"From Base32"}]"#
+        );
+
+        let recipe: Recipe = "a85|z85".parse().unwrap();
+        assert_eq!(
+            recipe.to_cyberchef_url().unwrap(),
+            "recipe=From_Base85('!-u',true,'z')From_Base85('0-9a-zA-Z.%5C%5C-:%2B%3D%5E!/*%3F%26%3C%3E()%5B%5D%7B%7D%40%25%24%23',true,'')"
+        );
+
+        let recipe: Recipe = "url|html|qp|uesc|xesc".parse().unwrap();
+        assert_eq!(
+            recipe.to_cyberchef_url().unwrap(),
+            "recipe=URL_Decode(false)From_HTML_Entity()From_Quoted_Printable()Unescape_Unicode_Characters('%5C%5Cu')From_Hex('%5C%5Cx')"
+        );
+
+        let recipe: Recipe = "cdec|coct(comma)|chex(comma,prefix)|cbin(newline)"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            recipe.to_cyberchef_url().unwrap(),
+            "recipe=From_Decimal('Space',false)From_Octal('Comma')From_Hex('0x%20with%20comma')From_Binary('Line%20feed',8)"
+        );
+
+        let recipe: Recipe = "hex|base10".parse().unwrap();
+        assert!(matches!(
+            recipe.to_cyberchef_json(),
+            Err(RecipeError::NotInCyberChef(step)) if step == "base10"
+        ));
+    }
+
+    #[test]
+    fn test_import() {
+        let recipe = Recipe::from_cyberchef(
+            r#"[
+                {"op": "From Hex", "args": ["None"]},
+                {"op": "To Upper case", "args": ["All"], "disabled": true},
+                {"op": "From Base64", "args": ["A-Za-z0-9-_", true, false]},
+                {"op": "From Base32"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(recipe.to_string(), "hex|base64url-nopad|base32");
+
+        // Round trip.
+        let json = recipe.to_cyberchef_json().unwrap();
+        assert_eq!(
+            Recipe::from_cyberchef(&json).unwrap().to_string(),
+            "hex|base64url-nopad|base32"
+        );
+
+        let recipe = Recipe::from_cyberchef(
+            r#"[
+                {"op": "From Decimal", "args": ["Comma", false]},
+                {"op": "From Hex", "args": ["0x"]},
+                {"op": "From Binary", "args": ["Space", 8]}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            recipe.to_string(),
+            "charcode-dec(comma)|charcode-hex(prefix,none)|charcode-bin"
+        );
+        let json = recipe.to_cyberchef_json().unwrap();
+        assert_eq!(
+            Recipe::from_cyberchef(&json).unwrap().to_string(),
+            "charcode-dec(comma)|charcode-hex(prefix,none)|charcode-bin"
+        );
+    }
+
+    #[test]
+    fn test_import_invalid() {
+        assert!(matches!(
+            Recipe::from_cyberchef(r#"[{"op": "ROT13", "args": [true, true, false, 13]}]"#),
+            Err(RecipeError::UnsupportedOperation(op)) if op.starts_with("ROT13")
+        ));
+        assert!(matches!(
+            Recipe::from_cyberchef(r#"[{"op": "From Base64", "args": ["A-Za-z0-9+\\-=", true]}]"#),
+            Err(RecipeError::UnsupportedOperation(_))
+        ));
+        assert!(matches!(
+            Recipe::from_cyberchef(r#"{"op": "From Hex"}"#),
+            Err(RecipeError::InvalidCyberChef(_))
+        ));
+        assert!(matches!(
+            Recipe::from_cyberchef("not json"),
+            Err(RecipeError::InvalidCyberChef(_))
+        ));
+        assert!(matches!(
+            Recipe::from_cyberchef("[]"),
+            Err(RecipeError::Empty)
+        ));
+    }
+}
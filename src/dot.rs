@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::{rank_leaves, CrackTree, Scorer};
+
+/// Maximum number of characters of the decoded data shown in a node.
+const PREVIEW_LEN: usize = 24;
+
+impl CrackTree {
+    /// Render the tree in the Graphviz DOT language, e.g. `dot -Tsvg crack.dot > crack.svg`.
+    ///
+    /// Each node shows its base, score and a preview of its decoded data.
+    /// The path to the best result ranked by the scorer is highlighted, the results matching the crib are filled
+    /// and the duplicated nodes are dashed and linked to their first occurrence.
+    /// The results verified by a checksum have a double border.
+    pub fn to_dot(&self, scorer: &dyn Scorer) -> String {
+        let best_path = rank_leaves(self, scorer)
+            .first()
+            .map(|best| self.tree.path_to_root(best.node).collect::<HashSet<_>>())
+            .unwrap_or_default();
+
+        let mut dot = String::from("digraph crack {\n");
+        dot.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+        for id in self.tree.pre_order(self.root()) {
+            let data = &self[id];
+            let label = format!(
+                "{} ({:.2})\n{}",
+                data.base.map_or("ciphertext", |base| base.name),
+                data.score,
+                data.preview(PREVIEW_LEN)
+            );
+
+            let mut attributes = vec![format!("label=\"{}\"", escape(&label))];
+            if best_path.contains(&id) {
+                attributes.push("color=red, penwidth=2".to_string());
+            }
+            if data.crib_match {
+                attributes.push("style=filled, fillcolor=palegreen".to_string());
+            } else if data.duplicate_of.is_some() {
+                attributes.push("style=dashed".to_string());
+            }
+            if data.checksum_verified {
+                attributes.push("peripheries=2".to_string());
+            }
+            writeln!(dot, "    {} [{}];", id, attributes.join(", ")).unwrap();
+
+            if let Some(parent) = self.tree.parent(id) {
+                if best_path.contains(&id) {
+                    writeln!(dot, "    {} -> {} [color=red, penwidth=2];", parent, id).unwrap();
+                } else {
+                    writeln!(dot, "    {} -> {};", parent, id).unwrap();
+                }
+            }
+            if let Some(first) = data.duplicate_of {
+                writeln!(
+                    dot,
+                    "    {} -> {} [style=dotted, constraint=false];",
+                    id, first
+                )
+                .unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Escape a string to be used as a quoted DOT identifier, keeping its line breaks.
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+    use crate::{crack, get_bases, CompositeScorer, CrackOptions};
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let options = CrackOptions {
+            min_score: 0.0,
+            ..Default::default()
+        };
+        let tree = crack(b"616263", &get_bases(), &options);
+        let dot = tree.to_dot(&CompositeScorer::english());
+
+        assert!(dot.starts_with("digraph crack {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("    0 [label=\"ciphertext (1.00)\\n616263\""));
+        // One node and one edge for each node, except the root node without parent.
+        let nodes = dot.lines().filter(|line| line.contains("[label=")).count();
+        let edges = dot
+            .lines()
+            .filter(|line| line.contains(" -> ") && !line.contains("dotted"))
+            .count();
+        assert_eq!(nodes, tree.size());
+        assert_eq!(edges, tree.size() - 1);
+        // The path to the best result is highlighted.
+        assert!(dot.contains("    0 -> 2 [color=red, penwidth=2];"));
+        assert!(dot.contains("style=dotted, constraint=false"));
+
+        // The highlighted path leads to the best result of the given scorer.
+        for scorer in [CompositeScorer::utf8(), CompositeScorer::english()] {
+            let dot = tree.to_dot(&scorer);
+            let best = rank_leaves(&tree, &scorer)[0].node;
+            let parent = tree.tree().parent(best).unwrap();
+            assert!(dot.contains(&format!("    {} -> {} [color=red", parent, best)));
+        }
+    }
+}
@@ -3,26 +3,50 @@
 #![warn(missing_docs)]
 
 use iterator_ext::IteratorExt;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::{
+    collections::{HashMap, HashSet},
     fmt::{self},
-    rc::Rc,
+    num::NonZeroUsize,
+    ops::{ControlFlow, Index},
+    time::{Duration, Instant},
 };
 
 mod modules;
 pub use modules::*;
 
-mod tree;
+mod scorers;
+pub use scorers::*;
+
+mod crib;
+pub use crib::Crib;
+
+mod cyberchef;
+
+mod recipe;
+pub use recipe::{Recipe, RecipeError, RecipeStep};
+
+mod strategy;
+use strategy::{Frontier, Pending};
+pub use strategy::{Strategy, StrategyError};
+
+mod dot;
+pub mod tree;
 mod utils;
-use tree::{RefNode, Tree};
+
+#[cfg(feature = "serde")]
+mod serialize;
+pub use tree::{NodeId, Tree};
 
 /// Encodes the given plaintext using the specified bases and return the result as a vector of steps.
 /// E.g. (plaintext, step1, step2, ..., ciphertext)
-pub fn encode(plaintext: &str, bases: &[Box<dyn Base>]) -> Vec<String> {
+pub fn encode(plaintext: &[u8], bases: &[Box<dyn Base>]) -> Vec<Vec<u8>> {
     bases
         .iter()
-        .scan(plaintext.to_string(), |text, base| {
-            let encoded = base.encode(text.as_bytes());
-            *text = encoded.clone();
+        .scan(plaintext.to_vec(), |data, base| {
+            let encoded = base.encode(data);
+            *data = encoded.clone();
             Some(encoded)
         })
         .collect()
@@ -30,13 +54,12 @@ pub fn encode(plaintext: &str, bases: &[Box<dyn Base>]) -> Vec<String> {
 
 /// Decodes the given ciphertext using the specified bases and return the result as a vector of steps.
 /// E.g. (ciphertext, step1, step2, ..., plaintext)
-pub fn decode(ciphertext: &str, bases: &[Box<dyn Base>]) -> Result<Vec<Vec<u8>>, DecodeError> {
+pub fn decode(ciphertext: &[u8], bases: &[Box<dyn Base>]) -> Result<Vec<Vec<u8>>, DecodeError> {
     bases
         .iter()
         .map(Ok)
-        .try_scan(ciphertext.as_bytes().to_vec(), |acc, base| {
-            let decoded =
-                base.decode(&String::from_utf8(acc.to_vec()).map_err(DecodeError::InvalidUtf8)?)?;
+        .try_scan(ciphertext.to_vec(), |acc, base| {
+            let decoded = base.decode(acc)?;
             *acc = decoded.clone();
             Ok(Some(decoded))
         })
@@ -45,13 +68,29 @@ pub fn decode(ciphertext: &str, bases: &[Box<dyn Base>]) -> Result<Vec<Vec<u8>>,
 
 /// Crack data.
 #[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CrackData {
     /// The base used to decode the ciphertext.
+    #[cfg_attr(feature = "serde", serde(with = "serialize::base_name"))]
     pub base: Option<&'static BaseMetadata>,
     /// The decoded data.
     pub decoded: Vec<u8>,
-    /// The percentage of printable characters in the decoded text.
-    pub printable_percentage: f32,
+    /// The composite score of the decoded data.
+    pub score: f32,
+    /// The score of the decoded data for each scorer.
+    /// With the `serde` feature, only the scores of the registered scorers can be deserialized.
+    #[cfg_attr(feature = "serde", serde(with = "serialize::scorer_names"))]
+    pub scores: Vec<(&'static str, f32)>,
+    /// Whether the decoded data matches the crib.
+    pub crib_match: bool,
+    /// Whether the decoded data was verified by a checksum of the base, e.g. the CRC of yEnc.
+    /// Verified data are kept whatever their score.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub checksum_verified: bool,
+    /// Id of the node with the same decoded data which is decoded further: the first one decoded,
+    /// else the shallowest one.
+    /// The node is not decoded any further as it would lead to the same subtree.
+    pub duplicate_of: Option<NodeId>,
 }
 
 impl fmt::Debug for CrackData {
@@ -67,78 +106,740 @@ impl fmt::Debug for CrackData {
                     &self.decoded
                 },
             )
+            .field("score", &self.score)
+            .field("crib_match", &self.crib_match)
+            .field("checksum_verified", &self.checksum_verified)
+            .field("duplicate_of", &self.duplicate_of)
             .finish()
     }
 }
 
+impl CrackData {
+    /// Get a one-line preview of the decoded data, truncated to the given number of characters.
+    /// Invalid UTF-8 sequences and control characters are replaced, so it can be displayed as is.
+    pub fn preview(&self, max_len: usize) -> String {
+        let decoded = String::from_utf8_lossy(&self.decoded);
+        let mut preview = decoded
+            .chars()
+            .take(max_len)
+            .map(|c| if c.is_control() { '.' } else { c })
+            .collect::<String>();
+        if decoded.chars().count() > max_len {
+            preview.push_str("...");
+        }
+        preview
+    }
+}
+
 /// Crack tree.
-pub type CrackTree = Tree<CrackData>;
-/// Crack node.
-pub type CrackNode = RefNode<CrackData>;
+/// With the `serde` feature, the ids of the duplicated nodes are checked on load.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CrackTree {
+    tree: Tree<CrackData>,
+    truncated: Option<Truncation>,
+}
+
+impl CrackTree {
+    /// Get the root node, containing the ciphertext.
+    pub fn root(&self) -> NodeId {
+        self.tree.root()
+    }
+
+    /// Get leaves of the tree.
+    pub fn leaves(&self) -> Vec<NodeId> {
+        self.tree.leaves()
+    }
+
+    /// Get the underlying tree, to navigate through the nodes.
+    pub fn tree(&self) -> &Tree<CrackData> {
+        &self.tree
+    }
+
+    /// Get the number of nodes, including the root node.
+    pub fn size(&self) -> usize {
+        self.tree.size()
+    }
+
+    /// Get the reason why the search was truncated, if it was.
+    pub fn truncated(&self) -> Option<Truncation> {
+        self.truncated
+    }
+}
+
+impl Index<NodeId> for CrackTree {
+    type Output = CrackData;
+
+    fn index(&self, id: NodeId) -> &CrackData {
+        &self.tree[id]
+    }
+}
+
+/// Reason why a crack search was truncated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Truncation {
+    /// Some nodes were not decoded because they reached the maximum depth.
+    MaxDepth,
+    /// The search was stopped because the tree reached the maximum number of nodes.
+    MaxNodes,
+    /// The search was stopped because it exceeded the timeout.
+    Timeout,
+    /// Some nodes were not decoded because they were pruned by the beam search.
+    BeamWidth,
+}
+
+impl fmt::Display for Truncation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Truncation::MaxDepth => write!(f, "maximum depth reached"),
+            Truncation::MaxNodes => write!(f, "maximum number of nodes reached"),
+            Truncation::Timeout => write!(f, "timeout reached"),
+            Truncation::BeamWidth => write!(f, "nodes pruned by the beam search"),
+        }
+    }
+}
+
+/// Crack options.
+pub struct CrackOptions {
+    /// Scorer used to rate the decoded data.
+    pub scorer: CompositeScorer,
+    /// Minimum composite score to keep a decoded data.
+    pub min_score: f32,
+    /// Known part of the plaintext.
+    /// The decoded data matching the crib are always kept and are not decoded any further.
+    pub crib: Option<Crib>,
+    /// Stop the search at the first decoded data matching the crib.
+    pub stop_on_crib: bool,
+    /// Maximum number of bases applied to the ciphertext.
+    pub max_depth: Option<usize>,
+    /// Maximum number of nodes in the tree, excluding the root node.
+    pub max_nodes: Option<usize>,
+    /// Maximum duration of the search.
+    pub timeout: Option<Duration>,
+    /// Order in which the decoded data are decoded further.
+    pub strategy: Strategy,
+    /// Number of decoded data kept at each depth by the beam search, the root node is always
+    /// decoded.
+    pub beam_width: NonZeroUsize,
+}
+
+impl Default for CrackOptions {
+    fn default() -> Self {
+        Self {
+            scorer: CompositeScorer::utf8(),
+            min_score: 0.9,
+            crib: None,
+            stop_on_crib: false,
+            max_depth: None,
+            max_nodes: None,
+            timeout: None,
+            strategy: Strategy::default(),
+            beam_width: NonZeroUsize::new(8).unwrap(),
+        }
+    }
+}
+
+/// State of a running crack search.
+struct CrackState {
+    tree: Tree<CrackData>,
+    start: Instant,
+    truncated: Option<Truncation>,
+    /// Decoded data already in the tree, and the id of the node decoded further.
+    seen: HashMap<Vec<u8>, NodeId>,
+    /// Nodes whose children were all added to the tree.
+    decoded: HashSet<NodeId>,
+}
+
+impl CrackState {
+    /// Record why the search was truncated, the reasons stopping the search take precedence, then
+    /// the pruning of the beam search.
+    fn truncate(&mut self, reason: Truncation) {
+        match (self.truncated, reason) {
+            (_, Truncation::MaxNodes | Truncation::Timeout)
+            | (None | Some(Truncation::MaxDepth), _) => self.truncated = Some(reason),
+            _ => {}
+        }
+    }
+
+    /// Record the decoded data of a node added to the tree, returns the id of a node with the same
+    /// data found before, if any.
+    /// A shallower node replaces the recorded one if it was not decoded yet, or only up to the
+    /// maximum depth: the recorded one becomes its duplicate, so each data is decoded only once.
+    fn see(&mut self, id: NodeId) -> Option<NodeId> {
+        let data = self.tree[id].decoded.clone();
+        match self.seen.get(&data) {
+            Some(&first)
+                if self.decoded.contains(&first)
+                    || self.tree.depth(first) <= self.tree.depth(id) =>
+            {
+                return Some(first)
+            }
+            Some(&first) => self.tree[first].duplicate_of = Some(id),
+            None => {}
+        }
+        self.seen.insert(data, id);
+        None
+    }
+}
 
 /// Cracks the given ciphertext using the specified bases and return the result as a tree of steps.
-pub fn crack(
-    ciphertext: &str,
-    bases: &[Box<dyn Base>],
-    min_printable_percentage: f32,
-) -> CrackTree {
-    let mut tree = CrackTree::new(CrackData {
-        base: None,
-        decoded: ciphertext.as_bytes().to_vec(),
-        printable_percentage: utils::printable_percentage(ciphertext.as_bytes()),
-    });
+pub fn crack(ciphertext: &[u8], bases: &[Box<dyn Base>], options: &CrackOptions) -> CrackTree {
+    let (score, scores) = options.scorer.evaluate(ciphertext);
+    let mut state = CrackState {
+        tree: Tree::new(CrackData {
+            base: None,
+            decoded: ciphertext.to_vec(),
+            score,
+            scores,
+            crib_match: false,
+            checksum_verified: false,
+            duplicate_of: None,
+        }),
+        start: Instant::now(),
+        truncated: None,
+        seen: HashMap::new(),
+        decoded: HashSet::new(),
+    };
+    state.see(state.tree.root());
 
-    crack_round(ciphertext, bases, min_printable_percentage, tree.root());
+    let mut frontier = Frontier::new(options.strategy, options.beam_width);
+    frontier.start(Pending {
+        node: state.tree.root(),
+        depth: 0,
+        score,
+    });
+    while let Some(pending) = frontier.pop() {
+        match crack_node(bases, options, &mut state, &pending) {
+            ControlFlow::Continue(children) => frontier.extend(children),
+            ControlFlow::Break(()) => break,
+        }
+    }
+    if frontier.pruned() {
+        state.truncate(Truncation::BeamWidth);
+    }
 
-    tree
+    CrackTree {
+        tree: state.tree,
+        truncated: state.truncated,
+    }
 }
 
-/// Iterates over the given bases and generates a tree of all possible combinations of good bases.
-pub fn crack_round(
-    ciphertext: &str,
+/// Decodes the given node with each base and adds the good results to the tree.
+/// Returns the children to decode further, or breaks if the search must be stopped.
+fn crack_node(
     bases: &[Box<dyn Base>],
-    min_printable_percentage: f32,
-    node: RefNode<CrackData>,
-) {
-    for base in bases {
-        let decoded = match base.decode(ciphertext) {
-            Ok(decoded) => decoded,
-            Err(_) => continue,
-        };
+    options: &CrackOptions,
+    state: &mut CrackState,
+    pending: &Pending,
+) -> ControlFlow<(), Vec<Pending>> {
+    if options
+        .timeout
+        .is_some_and(|timeout| state.start.elapsed() >= timeout)
+    {
+        state.truncate(Truncation::Timeout);
+        return ControlFlow::Break(());
+    }
 
-        let printable_percentage = utils::printable_percentage(&decoded);
+    // A shallower node with the same data was found since the node was added.
+    if state.tree[pending.node].duplicate_of.is_some() {
+        return ControlFlow::Continue(Vec::new());
+    }
 
-        if printable_percentage >= min_printable_percentage {
-            let data = CrackData {
-                base: Some(base.get_metadata()),
-                decoded: decoded.clone(),
-                printable_percentage,
-            };
+    let candidates = decode_candidates(bases, options, &state.tree[pending.node].decoded);
+    let mut children = Vec::new();
 
-            let child = tree::add_child(&node, data);
+    for data in candidates {
+        // The node has a child, but it can not be added.
+        if options.max_depth.is_some_and(|max| pending.depth >= max) {
+            state.truncate(Truncation::MaxDepth);
+            return ControlFlow::Continue(Vec::new());
+        }
+        if options.max_nodes.is_some_and(|max| state.tree.size() > max) {
+            state.truncate(Truncation::MaxNodes);
+            return ControlFlow::Break(());
+        }
 
-            // Recurse if the decoded data is valid UTF-8
-            if let Ok(decoded) = String::from_utf8(decoded) {
-                crack_round(&decoded, bases, min_printable_percentage, child);
+        let (score, crib_match) = (data.score, data.crib_match);
+        let child = state.tree.add_child(pending.node, data);
+        state.tree[child].duplicate_of = state.see(child);
+        if state.tree[child].duplicate_of.is_some() {
+            continue;
+        }
+        if crib_match {
+            if options.stop_on_crib {
+                return ControlFlow::Break(());
             }
+        } else {
+            children.push(Pending {
+                node: child,
+                depth: pending.depth + 1,
+                score,
+            });
         }
     }
+    state.decoded.insert(pending.node);
+    ControlFlow::Continue(children)
+}
+
+/// Decodes the data with each base, keeping the results accepted by the scorer, the crib or a
+/// checksum.
+/// The results are in the order of the bases, even when they are decoded in parallel.
+fn decode_candidates(
+    bases: &[Box<dyn Base>],
+    options: &CrackOptions,
+    data: &[u8],
+) -> Vec<CrackData> {
+    #[cfg(feature = "parallel")]
+    let bases = bases.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let bases = bases.iter();
+
+    bases
+        .filter_map(|base| {
+            let (decoded, checksum_verified) = base.decode_verified(data).ok()?;
+            let (score, scores) = options.scorer.evaluate(&decoded);
+            let crib_match = options
+                .crib
+                .as_ref()
+                .is_some_and(|crib| crib.is_match(&decoded));
+
+            (crib_match || checksum_verified || score >= options.min_score).then(|| CrackData {
+                base: Some(base.get_metadata()),
+                decoded,
+                score,
+                scores,
+                crib_match,
+                checksum_verified,
+                duplicate_of: None,
+            })
+        })
+        .collect()
 }
 
 /// Returns the base sequence of the given node including itself, until the root node.
-pub fn get_recipe(node: &CrackNode) -> Vec<Rc<CrackData>> {
-    let mut bases = vec![node.borrow().data.clone()];
-    let mut current = node.clone();
-
-    while let Some(parent) = &current.clone().borrow().parent {
-        // Ignore the first node.
-        if parent.borrow().parent.is_none() {
-            break;
-        }
-        bases.push(parent.borrow().data.clone());
-        current = parent.clone();
-    }
+pub fn get_recipe(tree: &CrackTree, node: NodeId) -> Vec<&CrackData> {
+    let mut bases = tree
+        .tree
+        .path_to_root(node)
+        // Ignore the root node, it was not decoded.
+        .filter(|&id| id != tree.root())
+        .map(|id| &tree[id])
+        .collect::<Vec<_>>();
     // Reverse the bases to get the decoding sequence.
     bases.reverse();
     bases
 }
+
+/// Crack result, a node of the crack tree with its recipe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrackResult<'a> {
+    /// The node of the crack tree, containing the plaintext.
+    pub node: NodeId,
+    /// The decoding sequence, from the first decoded data to the plaintext.
+    pub recipe: Vec<&'a CrackData>,
+    /// The score of the plaintext.
+    pub score: f32,
+}
+
+impl CrackResult<'_> {
+    /// Get the recipe decoding the ciphertext into the plaintext.
+    pub fn to_recipe(&self) -> Recipe {
+        Recipe::from_bases(self.recipe.iter().filter_map(|data| data.base))
+    }
+}
+
+/// Ranks the decoded data of the crack tree by the score of their plaintext,
+/// the leaves then the shortest recipe first in case of a tie.
+/// The inner nodes are ranked too, as a plaintext may still be decoded by a permissive base.
+/// The plaintexts matching the crib are always ranked first.
+/// The duplicated nodes are ignored, only their first occurrence is ranked.
+pub fn rank_leaves<'a>(tree: &'a CrackTree, scorer: &dyn Scorer) -> Vec<CrackResult<'a>> {
+    let mut results = tree
+        .tree
+        .pre_order(tree.root())
+        .skip(1)
+        .filter(|&id| tree[id].duplicate_of.is_none())
+        .map(|node| {
+            let recipe = get_recipe(tree, node);
+            let score = scorer.score(&recipe.last().unwrap().decoded);
+            CrackResult {
+                node,
+                recipe,
+                score,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    results.sort_by(|a, b| {
+        let crib_match = |result: &CrackResult<'_>| result.recipe.last().unwrap().crib_match;
+        crib_match(b)
+            .cmp(&crib_match(a))
+            .then(b.score.total_cmp(&a.score))
+            .then(tree.tree.is_leaf(b.node).cmp(&tree.tree.is_leaf(a.node)))
+            .then(a.recipe.len().cmp(&b.recipe.len()))
+    });
+    results
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_binary() {
+        let bases = get_bases_from_names(&["b64".to_string(), "hex".to_string()]).unwrap();
+        let plaintext = b"\x1f\x8b\x08\x00\xff\xfe";
+
+        let encoded = encode(plaintext, &bases);
+        assert_eq!(
+            encoded,
+            vec![b"H4sIAP/+".to_vec(), b"4834734941502f2b".to_vec()]
+        );
+
+        let mut bases = bases;
+        bases.reverse();
+        let decoded = decode(encoded.last().unwrap(), &bases).unwrap();
+        assert_eq!(decoded, vec![b"H4sIAP/+".to_vec(), plaintext.to_vec()]);
+    }
+
+    #[test]
+    fn test_rank_leaves() {
+        let ciphertext = b"dGhlIHNlY3JldCBpcyBoZXJl";
+        let options = CrackOptions {
+            min_score: 0.6,
+            ..Default::default()
+        };
+        let tree = crack(ciphertext, &get_bases(), &options);
+        let results = rank_leaves(&tree, &CompositeScorer::english());
+
+        // base62 comes first in the tree but decodes to garbage, as does z85.
+        assert_eq!(results.len(), 3);
+        let best = &results[0];
+        assert_eq!(best.recipe.len(), 1);
+        assert_eq!(best.recipe[0].base.unwrap().name, "base64");
+        assert_eq!(best.recipe[0].decoded, b"the secret is here");
+        assert!(results.windows(2).all(|w| w[0].score > w[1].score
+            || (w[0].score == w[1].score && w[0].recipe.len() <= w[1].recipe.len())));
+    }
+
+    #[test]
+    fn test_rank_inner_nodes() {
+        // "hi" is also valid base91, ascii85 and z85, it is not a leaf.
+        let options = CrackOptions {
+            min_score: 0.0,
+            max_depth: Some(3),
+            ..Default::default()
+        };
+        let tree = crack(b"aGk=", &get_bases(), &options);
+        let results = rank_leaves(&tree, &CompositeScorer::english());
+
+        let best = &results[0];
+        assert!(!tree.tree().is_leaf(best.node));
+        assert_eq!(best.recipe.len(), 1);
+        assert_eq!(best.recipe[0].decoded, b"hi");
+    }
+
+    #[test]
+    fn test_crack_crib() {
+        // base10 decodes to "Dd\x1aMD", not printable enough without the crib.
+        let ciphertext = b"293737221444";
+        let options = CrackOptions {
+            crib: Some("Dd".parse().unwrap()),
+            ..Default::default()
+        };
+        let tree = crack(ciphertext, &get_bases(), &options);
+        let results = rank_leaves(&tree, &CompositeScorer::english());
+
+        assert!(results[0].recipe.last().unwrap().crib_match);
+        assert_eq!(results[0].recipe.last().unwrap().decoded, b"Dd\x1aMD");
+    }
+
+    #[test]
+    fn test_crack_crib_stop() {
+        let ciphertext = b"ZmxhZ3t3ZWxsX2RvbmV9";
+        let options = CrackOptions {
+            min_score: 0.0,
+            crib: Some("flag{".parse().unwrap()),
+            stop_on_crib: true,
+            ..Default::default()
+        };
+        let tree = crack(ciphertext, &get_bases(), &options);
+        let leaves = tree.leaves();
+
+        let last = &tree[*leaves.last().unwrap()];
+        assert!(last.crib_match);
+        assert_eq!(last.decoded, b"flag{well_done}");
+        assert_eq!(
+            leaves.iter().filter(|&&leaf| tree[leaf].crib_match).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_crack_checksum() {
+        // Binary data is kept despite its low score, as the CRC of yEnc matches.
+        let plaintext = b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\x03";
+        let ciphertext = get_base_from_name("yenc").unwrap().encode(plaintext);
+        let tree = crack(&ciphertext, &get_bases(), &CrackOptions::default());
+
+        let verified = tree
+            .leaves()
+            .into_iter()
+            .filter(|&leaf| tree[leaf].checksum_verified)
+            .collect::<Vec<_>>();
+        assert_eq!(verified.len(), 1);
+        assert_eq!(tree[verified[0]].decoded, plaintext);
+        assert!(tree[verified[0]].score < CrackOptions::default().min_score);
+    }
+
+    #[test]
+    fn test_crack_max_depth() {
+        let bases = get_bases_from_names(&["b64".to_string(), "hex".to_string()]).unwrap();
+        let ciphertext = encode(b"hello", &bases).pop().unwrap();
+
+        let options = CrackOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        let tree = crack(&ciphertext, &get_bases(), &options);
+        assert_eq!(tree.truncated(), Some(Truncation::MaxDepth));
+        assert!(tree
+            .leaves()
+            .into_iter()
+            .all(|leaf| get_recipe(&tree, leaf).len() == 1));
+
+        let options = CrackOptions {
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        let tree = crack(&ciphertext, &get_bases(), &options);
+        // Z85 decodes "hello" to "5jXu", a valid result which could be decoded further.
+        assert_eq!(tree.truncated(), Some(Truncation::MaxDepth));
+        assert!(tree.leaves().into_iter().any(|leaf| {
+            let recipe = get_recipe(&tree, leaf);
+            recipe.len() == 2 && recipe[1].decoded == b"hello"
+        }));
+
+        // Without z85, "hello" can not be decoded further and the search is complete.
+        let tree = crack(&ciphertext, &bases, &options);
+        assert_eq!(tree.truncated(), None);
+        let results = rank_leaves(&tree, &options.scorer);
+        assert_eq!(results[0].recipe.last().unwrap().decoded, b"hello");
+    }
+
+    #[test]
+    fn test_crack_max_nodes() {
+        let options = CrackOptions {
+            min_score: 0.0,
+            max_nodes: Some(5),
+            ..Default::default()
+        };
+        let tree = crack(b"0101010101010101", &get_bases(), &options);
+        assert_eq!(tree.truncated(), Some(Truncation::MaxNodes));
+        assert!(tree.leaves().len() <= 5);
+    }
+
+    #[test]
+    fn test_crack_timeout() {
+        let options = CrackOptions {
+            min_score: 0.0,
+            timeout: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        let tree = crack(b"0101010101010101", &get_bases(), &options);
+        assert_eq!(tree.truncated(), Some(Truncation::Timeout));
+        assert!(tree.leaves().is_empty());
+    }
+
+    #[test]
+    fn test_crack_duplicates() {
+        let options = CrackOptions {
+            min_score: 0.0,
+            ..Default::default()
+        };
+        let tree = crack(b"616263", &get_bases(), &options);
+
+        let duplicates = tree
+            .leaves()
+            .into_iter()
+            .filter_map(|leaf| tree[leaf].duplicate_of.map(|first| (leaf, first)))
+            .collect::<Vec<_>>();
+        assert!(!duplicates.is_empty());
+        assert!(duplicates
+            .iter()
+            .all(|&(id, first)| first != id && tree[first].decoded == tree[id].decoded));
+
+        // Each decoded data is ranked only once.
+        let results = rank_leaves(&tree, &CompositeScorer::english());
+        let mut plaintexts = results
+            .iter()
+            .map(|result| result.recipe.last().unwrap().decoded.clone())
+            .collect::<Vec<_>>();
+        plaintexts.sort();
+        plaintexts.dedup();
+        assert_eq!(plaintexts.len(), results.len());
+    }
+
+    #[test]
+    fn test_crack_duplicates_shallowest() {
+        let options = CrackOptions {
+            min_score: 0.0,
+            max_depth: Some(5),
+            ..Default::default()
+        };
+        let tree = crack(b"ZmxhZw==", &get_bases(), &options);
+        let depth = |id| tree.tree().depth(id);
+
+        // A node found after a deeper node with the same data replaces it, so it is decoded
+        // further even when the deeper node reached the maximum depth.
+        let nodes = tree.tree().pre_order(tree.root()).collect::<Vec<_>>();
+        assert!(nodes
+            .iter()
+            .any(|&id| tree[id].duplicate_of.is_some_and(|first| first > id)));
+        // A deeper node is kept when it was decoded, before the maximum depth or without children.
+        for &id in &nodes {
+            if let Some(first) = tree[id].duplicate_of {
+                assert!(
+                    depth(first) <= depth(id)
+                        || depth(first) < 5
+                        || decode_candidates(&get_bases(), &options, &tree[first].decoded)
+                            .is_empty()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_crack_duplicates_decoded_once() {
+        let options = CrackOptions {
+            min_score: 0.0,
+            max_depth: Some(6),
+            strategy: Strategy::DepthFirst,
+            ..Default::default()
+        };
+        let tree = crack(b"ZmxhZw==", &get_bases(), &options);
+        let nodes = tree.tree().pre_order(tree.root()).collect::<Vec<_>>();
+
+        // Some data are reached at different depths, but only one of their nodes has children.
+        let mut depths = HashMap::<&[u8], HashSet<usize>>::new();
+        let mut decoded = HashSet::new();
+        for &id in &nodes {
+            let data = tree[id].decoded.as_slice();
+            depths
+                .entry(data)
+                .or_default()
+                .insert(tree.tree().depth(id));
+            if !tree.tree().is_leaf(id) {
+                assert!(tree[id].duplicate_of.is_none());
+                assert!(decoded.insert(data));
+            }
+        }
+        assert!(depths.values().any(|depths| depths.len() > 1));
+    }
+
+    #[test]
+    fn test_crack_strategies() {
+        let bases = get_bases_from_names(
+            &[
+                "b64", "hex", "b32", "b58", "b64", "b62", "hex", "b64", "b32", "b85", "b64",
+            ]
+            .map(String::from),
+        )
+        .unwrap();
+        let ciphertext = encode(b"flag{deep_onion}", &bases).pop().unwrap();
+
+        for strategy in [
+            Strategy::DepthFirst,
+            Strategy::BreadthFirst,
+            Strategy::BestFirst,
+            Strategy::Beam,
+        ] {
+            let options = CrackOptions {
+                strategy,
+                ..Default::default()
+            };
+            let tree = crack(&ciphertext, &get_bases(), &options);
+            let results = rank_leaves(&tree, &CompositeScorer::english());
+
+            let best = &results[0];
+            assert_eq!(best.recipe.len(), bases.len(), "{strategy}");
+            assert_eq!(
+                best.recipe.last().unwrap().decoded,
+                b"flag{deep_onion}",
+                "{strategy}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_crack_beam_width() {
+        let options = CrackOptions {
+            strategy: Strategy::Beam,
+            beam_width: NonZeroUsize::MIN,
+            min_score: 0.0,
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        let tree = crack(b"68656c6c6f", &get_bases(), &options);
+        assert_eq!(tree.truncated(), Some(Truncation::BeamWidth));
+
+        // A beam wide enough to keep every node is the breadth-first search.
+        let options = CrackOptions {
+            beam_width: NonZeroUsize::new(1000).unwrap(),
+            ..options
+        };
+        let tree = crack(b"68656c6c6f", &get_bases(), &options);
+        assert_eq!(tree.truncated(), Some(Truncation::MaxDepth));
+    }
+
+    #[test]
+    fn test_crack_deterministic() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<CrackTree>();
+
+        let options = CrackOptions {
+            min_score: 0.0,
+            max_depth: Some(3),
+            ..Default::default()
+        };
+        let ids = |tree: &CrackTree| {
+            tree.leaves()
+                .into_iter()
+                .map(|leaf| (leaf, tree[leaf].base.map(|base| base.name)))
+                .collect::<Vec<_>>()
+        };
+
+        let first = ids(&crack(b"4d6a4d334d7a55334e6a4d3d", &get_bases(), &options));
+        for _ in 0..5 {
+            let tree = crack(b"4d6a4d334d7a55334e6a4d3d", &get_bases(), &options);
+            assert_eq!(ids(&tree), first);
+        }
+    }
+
+    #[test]
+    fn test_preview() {
+        let data = |decoded: &[u8]| CrackData {
+            base: None,
+            decoded: decoded.to_vec(),
+            score: 0.0,
+            scores: vec![],
+            crib_match: false,
+            checksum_verified: false,
+            duplicate_of: None,
+        };
+        assert_eq!(data(b"hello").preview(5), "hello");
+        assert_eq!(data(b"hello world").preview(5), "hello...");
+        assert_eq!(data(b"a\nb\xffc").preview(8), "a.b\u{fffd}c");
+    }
+
+    #[test]
+    fn test_decode_invalid_utf8() {
+        let bases = get_bases_from_names(&["b32".to_string()]).unwrap();
+        assert!(matches!(
+            decode(b"\xff\xfe", &bases),
+            Err(DecodeError::InvalidUtf8(_))
+        ));
+    }
+}
@@ -1,9 +1,18 @@
-use clap::{command, Parser, Subcommand};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::{builder::RangedU64ValueParser, Parser, Subcommand, ValueEnum};
 use main_error::MainError;
-use std::path::Path;
-use std::{io, io::Write};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::{io, io::Read, io::Write};
 
-use basecracker::{crack, decode, encode, get_recipe, BaseError, BaseMetadata, DecodeError};
+use basecracker::{
+    crack, decode, encode, rank_leaves, BaseMetadata, CrackOptions, CrackResult, CrackTree, Crib,
+    Recipe, RecipeError, Strategy, Truncation,
+};
+use std::time::Duration;
 
 #[derive(Parser, Debug, Clone)]
 #[command(author, version, about)]
@@ -24,114 +33,185 @@ struct Options {
     /// Verbose mode
     #[clap(short, long, conflicts_with = "quiet")]
     verbose: bool,
-    /// Minimum printable percentage to consider a result valid
-    #[clap(short, long, default_value = "0.9")]
-    min_printable_percentage: f32,
+    /// Minimum score to consider a result valid
+    #[clap(short, long, default_value = "0.9", alias = "min-printable-percentage")]
+    min_score: f32,
+    /// Scorers used to rate results, with an optional weight (e.g. `utf8:2,dictionary`)
+    #[clap(long, default_value = "utf8")]
+    scorers: String,
     /// Do not output the trailing newline
     #[clap(short, long)]
     no_newline: bool,
+    /// Output format
+    #[clap(long, value_enum, default_value = "text")]
+    format: Format,
+}
+
+/// Output format.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// Human readable output
+    Text,
+    /// A single JSON document
+    Json,
+    /// One JSON document per line, for each step or result
+    Jsonl,
+}
+
+/// Input options.
+#[derive(Parser, Debug, Clone)]
+struct InputOptions {
+    /// Always read the input from the given file
+    #[clap(short, long, conflicts_with = "string")]
+    file: bool,
+    /// Always use the input as is, even if a file with the same name exists
+    #[clap(short, long, conflicts_with = "file")]
+    string: bool,
 }
 
 /// Subcommands.
 #[derive(Subcommand, Debug, Clone)]
 enum SubCommand {
-    /// Encode given plaintext/file using the specified bases
+    /// Encode given plaintext/file/stdin using the specified bases
+    #[command(allow_missing_positional = true)]
     Encode {
-        /// The plaintext to encode (can be a file)
-        plaintext: String,
-        /// The bases to use (can be separated by comma or space)
-        bases: Bases,
+        /// The plaintext to encode (can be a file, `-` or omitted to read stdin)
+        plaintext: Option<String>,
+        /// The bases to use, e.g. `base64(url)|hex`, a `.recipe` file or a CyberChef recipe
+        #[clap(value_parser = parse_recipe)]
+        bases: Recipe,
         /// Reverse the order of the bases
         #[clap(short, long)]
         reverse: bool,
+        #[clap(flatten)]
+        input: InputOptions,
     },
-    /// Decode given cipher/file using the specified bases
+    /// Decode given cipher/file/stdin using the specified bases
+    #[command(allow_missing_positional = true)]
     Decode {
-        /// The cipher to decode (can be a file)
-        ciphertext: String,
-        /// The bases to use (can be separated by comma or space)
-        bases: Bases,
+        /// The cipher to decode (can be a file, `-` or omitted to read stdin)
+        ciphertext: Option<String>,
+        /// The bases to use, e.g. `base64(url)|hex`, a `.recipe` file or a CyberChef recipe
+        #[clap(value_parser = parse_recipe)]
+        bases: Recipe,
         /// Reverse the order of the bases
         #[clap(short, long)]
         reverse: bool,
+        #[clap(flatten)]
+        input: InputOptions,
     },
-    /// Crack given cipher/file
+    /// Crack given cipher/file/stdin
     Crack {
-        /// The cipher to crack (can be a file)
-        ciphertext: String,
+        /// The cipher to crack (can be a file, `-` or omitted to read stdin)
+        ciphertext: Option<String>,
+        /// Only show the N most plausible results
+        #[clap(short, long, value_name = "N", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        top: Option<usize>,
+        /// Known part of the plaintext, as a regex or a substring (e.g. `flag\{.*\}` or `HTB{`)
+        #[clap(short, long)]
+        crib: Option<Crib>,
+        /// Stop the search as soon as a result matches the crib
+        #[clap(long, requires = "crib")]
+        crib_stop: bool,
+        /// Maximum number of bases applied to the cipher
+        #[clap(long, value_name = "N")]
+        max_depth: Option<usize>,
+        /// Maximum number of decoded data explored
+        #[clap(long, value_name = "N")]
+        max_nodes: Option<usize>,
+        /// Stop the search after the given number of seconds
+        #[clap(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+        /// Search strategy: dfs, bfs, best or beam
+        #[clap(long, default_value = "dfs")]
+        strategy: Strategy,
+        /// Number of results kept at each depth by the beam search
+        #[clap(long, value_name = "K", default_value = "8")]
+        beam_width: NonZeroUsize,
+        /// Write the crack tree to the given file, in the Graphviz DOT format
+        #[clap(long, value_name = "FILE")]
+        dot: Option<PathBuf>,
+        /// Print the whole crack tree instead of the results
+        #[clap(long)]
+        tree: bool,
+        /// Do not print the rejected branches, only leading to duplicated data
+        #[clap(long, requires = "tree")]
+        collapse: bool,
+        /// Only print the nodes up to the given depth
+        #[clap(long, value_name = "N", requires = "tree")]
+        tree_depth: Option<usize>,
+        #[clap(flatten)]
+        input: InputOptions,
     },
 }
 
-#[derive(Debug, Clone)]
-struct Bases(Vec<String>);
-
-impl std::str::FromStr for Bases {
-    type Err = String;
-
-    #[cfg(not(tarpaulin_include))]
-    fn from_str(bases: &str) -> Result<Self, Self::Err> {
-        let bases = bases
-            // Split by comma
-            .split(',')
-            .map(|base| base.to_string())
-            .collect::<Vec<String>>()
-            // Split by space
-            .iter()
-            .flat_map(|base| base.split(' ').map(|b| b.to_string()))
-            .collect::<Vec<String>>()
-            // Remove empty bases
-            .iter()
-            .map(|base| base.trim())
-            .filter(|base| !base.is_empty())
-            .map(|base| base.to_string())
-            .collect::<Vec<String>>()
-            // Check if all bases are valid
-            .iter()
-            .map(|base| {
-                if basecracker::get_base_from_name(base).is_err() {
-                    Err(format!("Invalid base: {}", base))
-                } else {
-                    Ok(base.to_string())
-                }
-            })
-            .collect::<Result<Vec<String>, String>>()?;
-
-        // Check if there is at least one base
-        if bases.is_empty() {
-            Err("No base specified".to_string())
-        } else {
-            Ok(())
-        }?;
+/// Parse a recipe, or load it from a file with the `.recipe` extension.
+/// CyberChef recipes are accepted in JSON, directly or in a file with the `.json` extension.
+/// The bases are checked, so that an invalid recipe is reported as a usage error.
+#[cfg(not(tarpaulin_include))]
+fn parse_recipe(recipe: &str) -> Result<Recipe, RecipeError> {
+    let recipe = if recipe.ends_with(".recipe") {
+        Recipe::from_file(recipe)?
+    } else if recipe.ends_with(".json") {
+        Recipe::from_cyberchef(&std::fs::read_to_string(recipe)?)?
+    } else if recipe.trim_start().starts_with('[') {
+        Recipe::from_cyberchef(recipe)?
+    } else {
+        recipe.parse::<Recipe>()?
+    };
+    recipe.bases()?;
+    Ok(recipe)
+}
 
-        Ok(Bases(bases))
-    }
+/// Parse a timeout in seconds, which must be finite and not negative.
+#[cfg(not(tarpaulin_include))]
+fn parse_timeout(seconds: &str) -> Result<Duration, String> {
+    let seconds = seconds.parse::<f64>().map_err(|e| e.to_string())?;
+    Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string())
 }
 
-/// If argument is a file, read it and return its content, else return the argument as is
+/// Read the input from stdin, a file or the argument itself.
+/// `-` or a missing argument reads stdin, unless `--string` is set.
+/// Without `--file` nor `--string`, the argument is read as a file if it exists, else it is used as is.
 #[cfg(not(tarpaulin_include))]
-fn read_file_or_arg(arg: String) -> String {
-    if Path::new(&arg).exists() {
-        std::fs::read_to_string(arg).unwrap()
-    } else {
-        arg
+fn read_input(arg: Option<String>, options: &InputOptions) -> io::Result<Vec<u8>> {
+    match arg {
+        Some(arg) if options.string => Ok(arg.into_bytes()),
+        Some(arg) if arg != "-" => {
+            if options.file || Path::new(&arg).exists() {
+                std::fs::read(arg)
+            } else {
+                Ok(arg.into_bytes())
+            }
+        }
+        _ => {
+            let mut buf = Vec::new();
+            io::stdin().read_to_end(&mut buf)?;
+            Ok(buf)
+        }
     }
 }
 
-#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
-enum Error {
-    #[error(transparent)]
-    DecodeError(#[from] DecodeError),
-    #[error(transparent)]
-    BaseError(#[from] BaseError),
+/// Remove the trailing newline of an encoded input, usually added by `echo` or text editors.
+#[cfg(not(tarpaulin_include))]
+fn trim_newline(mut data: Vec<u8>) -> Vec<u8> {
+    if data.ends_with(b"\n") {
+        data.pop();
+        if data.ends_with(b"\r") {
+            data.pop();
+        }
+    }
+    data
 }
 
+/// Split a crack result into its bases and decoded data.
 #[cfg(not(tarpaulin_include))]
-fn base_to_recipe(bases: &[&BaseMetadata]) -> String {
-    bases
+fn split_crack_result<'a>(result: &CrackResult<'a>) -> (Vec<&'static BaseMetadata>, Vec<&'a [u8]>) {
+    result
+        .recipe
         .iter()
-        .map(|b| b.name)
-        .collect::<Vec<&'static str>>()
-        .join(",")
+        .map(|data| (data.base.unwrap(), data.decoded.as_slice()))
+        .unzip()
 }
 
 #[cfg(not(tarpaulin_include))]
@@ -157,6 +237,154 @@ fn display_result(
     Ok(())
 }
 
+/// Display the steps of an encoding or a decoding in the requested format.
+#[cfg(not(tarpaulin_include))]
+fn display_steps(
+    result: &[&[u8]],
+    bases: &[&BaseMetadata],
+    options: &Options,
+) -> std::io::Result<()> {
+    let steps = result
+        .iter()
+        .zip(bases.iter())
+        .map(|(data, base)| json!({ "base": base.name, "data": data_to_json(data) }));
+
+    match options.format {
+        Format::Text => display_result(result, bases, options)?,
+        Format::Json => {
+            let output = json!({
+                "steps": steps.collect::<Vec<_>>(),
+                "result": data_to_json(result.last().unwrap()),
+            });
+            println!("{:#}", output);
+        }
+        Format::Jsonl => steps.for_each(|step| println!("{}", step)),
+    }
+    Ok(())
+}
+
+/// Print the crack tree like `tree(1)`, with the base, score and preview of each node.
+/// When collapsed, the rejected branches, whose leaves are all duplicates, are not printed and
+/// only counted on their parent.
+#[cfg(not(tarpaulin_include))]
+fn display_tree(tree: &CrackTree, collapse: bool, max_depth: Option<usize>) {
+    // The nodes leading to a result, i.e. a leaf which is not a duplicate.
+    let mut accepted = HashSet::new();
+    for id in tree.tree().post_order(tree.root()) {
+        if tree.tree().is_leaf(id) && tree[id].duplicate_of.is_none()
+            || tree
+                .tree()
+                .children(id)
+                .iter()
+                .any(|child| accepted.contains(child))
+        {
+            accepted.insert(id);
+        }
+    }
+    let filter = |id| {
+        (!collapse || accepted.contains(&id))
+            && max_depth.is_none_or(|max| tree.tree().depth(id) <= max)
+    };
+
+    for visit in tree.tree().walk(tree.root(), filter) {
+        let mut line = String::new();
+        if let Some((last, ancestors)) = visit.last.split_last() {
+            for ancestor_last in ancestors {
+                line.push_str(if *ancestor_last { "    " } else { "│   " });
+            }
+            line.push_str(if *last { "└── " } else { "├── " });
+        }
+
+        let data = &tree[visit.id];
+        write!(
+            line,
+            "#{} {} ({:.2}) {}",
+            visit.id,
+            data.base.map_or("ciphertext", |base| base.name),
+            data.score,
+            data.preview(48)
+        )
+        .unwrap();
+        if let Some(first) = data.duplicate_of {
+            write!(line, " (duplicate of #{})", first).unwrap();
+        }
+        if data.crib_match {
+            line.push_str(" (crib)");
+        }
+        if data.checksum_verified {
+            line.push_str(" (checksum)");
+        }
+        let rejected = tree
+            .tree()
+            .children(visit.id)
+            .iter()
+            .filter(|child| !accepted.contains(child))
+            .count();
+        if collapse && rejected > 0 {
+            write!(line, " (+{} rejected)", rejected).unwrap();
+        }
+        println!("{}", line);
+    }
+}
+
+/// JSON representation of some data, base64-wrapped when it is not UTF-8.
+#[cfg(not(tarpaulin_include))]
+fn data_to_json(data: &[u8]) -> Value {
+    match std::str::from_utf8(data) {
+        Ok(text) => json!(text),
+        Err(_) => json!({ "base64": STANDARD.encode(data) }),
+    }
+}
+
+/// JSON representation of a score, rounded to avoid the f32 to f64 conversion noise.
+#[cfg(not(tarpaulin_include))]
+fn score_to_json(score: f32) -> Value {
+    json!((f64::from(score) * 1e4).round() / 1e4)
+}
+
+/// JSON representation of a crack result, with the intermediate data of each step.
+#[cfg(not(tarpaulin_include))]
+fn crack_result_to_json(result: &CrackResult<'_>) -> Value {
+    let plaintext = result.recipe.last().unwrap();
+    let steps = result
+        .recipe
+        .iter()
+        .map(|data| {
+            json!({
+                "base": data.base.unwrap().name,
+                "data": data_to_json(&data.decoded),
+                "score": score_to_json(data.score),
+                "checksum_verified": data.checksum_verified,
+                "scores": data
+                    .scores
+                    .iter()
+                    .map(|(name, score)| (name.to_string(), score_to_json(*score)))
+                    .collect::<serde_json::Map<_, _>>(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "recipe": result.to_recipe().to_string(),
+        "score": score_to_json(result.score),
+        "crib_match": plaintext.crib_match,
+        "plaintext": data_to_json(&plaintext.decoded),
+        "steps": steps,
+    })
+}
+
+/// Machine readable name of a truncation reason.
+#[cfg(not(tarpaulin_include))]
+fn truncation_to_json(reason: Option<Truncation>) -> Value {
+    match reason {
+        None => Value::Null,
+        Some(Truncation::MaxDepth) => json!("max_depth"),
+        Some(Truncation::MaxNodes) => json!("max_nodes"),
+        Some(Truncation::Timeout) => json!("timeout"),
+        Some(Truncation::BeamWidth) => json!("beam_width"),
+    }
+}
+
 #[cfg(not(tarpaulin_include))]
 fn main() -> Result<(), MainError> {
     let args = Args::parse();
@@ -166,9 +394,10 @@ fn main() -> Result<(), MainError> {
             plaintext,
             bases,
             reverse,
+            input,
         } => {
-            let plaintext = read_file_or_arg(plaintext);
-            let mut bases = basecracker::get_bases_from_names(&bases.0)?;
+            let plaintext = read_input(plaintext, &input)?;
+            let mut bases = bases.bases()?;
             if reverse {
                 bases.reverse();
             }
@@ -178,10 +407,10 @@ fn main() -> Result<(), MainError> {
                 .iter()
                 .map(|base| base.get_metadata())
                 .collect::<Vec<_>>();
-            display_result(
+            display_steps(
                 &result
                     .iter()
-                    .map(|data| data.as_bytes())
+                    .map(|data| data.as_slice())
                     .collect::<Vec<_>>(),
                 &bases,
                 &args.options,
@@ -191,9 +420,10 @@ fn main() -> Result<(), MainError> {
             ciphertext,
             bases,
             reverse,
+            input,
         } => {
-            let ciphertext = read_file_or_arg(ciphertext);
-            let mut bases = basecracker::get_bases_from_names(&bases.0)?;
+            let ciphertext = trim_newline(read_input(ciphertext, &input)?);
+            let mut bases = bases.bases()?;
             if reverse {
                 bases.reverse();
             }
@@ -203,7 +433,7 @@ fn main() -> Result<(), MainError> {
                 .iter()
                 .map(|base| base.get_metadata())
                 .collect::<Vec<_>>();
-            display_result(
+            display_steps(
                 &result
                     .iter()
                     .map(|data| data.as_slice())
@@ -212,50 +442,91 @@ fn main() -> Result<(), MainError> {
                 &args.options,
             )?;
         }
-        SubCommand::Crack { ciphertext } => {
-            let ciphertext = read_file_or_arg(ciphertext);
+        SubCommand::Crack {
+            ciphertext,
+            top,
+            crib,
+            crib_stop,
+            max_depth,
+            max_nodes,
+            timeout,
+            strategy,
+            beam_width,
+            dot,
+            tree: print_tree,
+            collapse,
+            tree_depth,
+            input,
+        } => {
+            let ciphertext = trim_newline(read_input(ciphertext, &input)?);
 
-            let result = crack(
-                &ciphertext,
-                &basecracker::get_bases(),
-                args.options.min_printable_percentage,
-            );
-            let leaves = result.leaves();
+            let options = CrackOptions {
+                scorer: args.options.scorers.parse()?,
+                min_score: args.options.min_score,
+                crib,
+                stop_on_crib: crib_stop,
+                max_depth,
+                max_nodes,
+                timeout,
+                strategy,
+                beam_width,
+            };
+            let tree = crack(&ciphertext, &basecracker::get_bases(), &options);
+            if let Some(path) = dot {
+                std::fs::write(path, tree.to_dot(&options.scorer))?;
+            }
+            if let Some(reason) = tree.truncated() {
+                if !args.options.quiet {
+                    eprintln!("Warning: search truncated, {}", reason);
+                }
+            }
+            let results = rank_leaves(&tree, &options.scorer);
+            let total = results.len();
+            let results = &results[..top.unwrap_or(total).min(total)];
 
-            if leaves.is_empty() {
+            if print_tree {
+                display_tree(&tree, collapse, tree_depth);
+            } else if args.options.format == Format::Json {
+                let output = json!({
+                    "truncated": truncation_to_json(tree.truncated()),
+                    "total": total,
+                    "results": results.iter().map(crack_result_to_json).collect::<Vec<_>>(),
+                });
+                println!("{:#}", output);
+            } else if args.options.format == Format::Jsonl {
+                for result in results {
+                    println!("{}", crack_result_to_json(result));
+                }
+                // The summary comes last, once the results are streamed.
+                let summary = json!({
+                    "truncated": truncation_to_json(tree.truncated()),
+                    "total": total,
+                });
+                println!("{}", summary);
+            } else if results.is_empty() {
                 // No result found
                 eprintln!("Error: No result found");
-            } else if leaves.len() == 1 {
+            } else if results.len() == 1 {
                 // One result found (no ambiguity)
-                let leaf = leaves[0].clone();
-                let recipe = get_recipe(&leaf);
-                let bases = recipe
-                    .iter()
-                    .map(|data| data.base.unwrap())
-                    .collect::<Vec<_>>();
-                let result = recipe
-                    .iter()
-                    .map(|data| data.decoded.as_slice())
-                    .collect::<Vec<_>>();
+                let (bases, result) = split_crack_result(&results[0]);
                 if !args.options.quiet {
-                    eprintln!("Recipe: {}", base_to_recipe(&bases));
+                    eprintln!("Recipe: {}", results[0].to_recipe());
                 }
                 display_result(&result, &bases, &args.options)?;
             } else {
-                // Multiple results found
-                eprintln!("Warning: {} results found, you may want to use the --min-printable-percentage option", leaves.len());
-                for leaf in leaves {
-                    let recipe = get_recipe(&leaf);
-                    let bases = recipe
-                        .iter()
-                        .map(|data| data.base.unwrap())
-                        .collect::<Vec<_>>();
-                    let result = recipe
-                        .iter()
-                        .map(|data| data.decoded.as_slice())
-                        .collect::<Vec<_>>();
-                    println!("Recipe: {}", base_to_recipe(&bases));
-                    display_result(&result, &bases, &args.options)?;
+                // Multiple results found, the most plausible first
+                eprintln!(
+                    "Warning: {} results found, you may want to use the --top or --min-score options",
+                    total
+                );
+                for result in results {
+                    let (bases, data) = split_crack_result(result);
+                    println!(
+                        "Recipe: {} (score: {:.2})",
+                        result.to_recipe(),
+                        result.score
+                    );
+                    display_result(&data, &bases, &args.options)?;
                     println!();
                 }
             }
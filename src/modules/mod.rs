@@ -1,20 +1,35 @@
-use std::string::FromUtf8Error;
+use std::str::Utf8Error;
 
 use thiserror::Error;
 
 mod module_base10;
+mod module_base100;
 mod module_base2;
+mod module_base2048;
 mod module_base32;
 mod module_base36;
+mod module_base45;
 mod module_base58;
 mod module_base62;
 mod module_base64;
+mod module_base65536;
 mod module_base85;
+mod module_base91;
+mod module_binhex;
+mod module_charcode;
+mod module_escape;
 mod module_hex;
+mod module_html;
+mod module_quoted_printable;
+mod module_url;
+mod module_uuencode;
+mod module_yenc;
 
 /// Base Metadata.
 /// It contains the name, short name, base, and padding of a base.
+/// With the `serde` feature, it is deserialized by looking up its name in the registry.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BaseMetadata {
     /// Name of the base.
     pub name: &'static str,
@@ -40,25 +55,31 @@ pub enum DecodeError {
     /// canonical, or present when it must be absent, etc.
     #[error("Invalid padding")]
     InvalidPadding,
-    /// The encoded string is not a valid UTF-8 string.
+    /// The checksum of the encoded data does not match the decoded data.
+    #[error("Invalid checksum")]
+    InvalidChecksum,
+    /// The encoded data is not a valid UTF-8 string.
     #[error("Invalid UTF-8")]
-    InvalidUtf8(#[from] FromUtf8Error),
+    InvalidUtf8(#[from] Utf8Error),
     /// Generic error.
     #[error("Can not decode the provided data")]
     Error,
 }
 
-/// A base encoding/decoding module.
-pub trait Base {
+/// A base encoding/decoding module, shareable between threads.
+pub trait Base: Send + Sync {
     /// Get the metadata of the base.
     fn get_metadata(&self) -> &'static BaseMetadata;
 
-    /// Check if the encoded string looks like it is encoded with this base.
-    /// This function does not check if the decoded string is actually valid.
-    fn is_valid(&self, encoded: &str) -> bool {
+    /// Check if the encoded data looks like it is encoded with this base.
+    /// This function does not check if the decoded data is actually valid.
+    fn is_valid(&self, encoded: &[u8]) -> bool {
         let metadata = self.get_metadata();
         let (base, padding) = (metadata.base, metadata.padding);
 
+        let Ok(encoded) = std::str::from_utf8(encoded) else {
+            return false;
+        };
         let mut is_padding = false;
 
         for c in encoded.chars() {
@@ -77,11 +98,94 @@ pub trait Base {
         true
     }
 
-    /// Encode a string.
-    fn encode(&self, plain: &[u8]) -> String;
+    /// Encode data.
+    fn encode(&self, plain: &[u8]) -> Vec<u8>;
 
-    /// Decode a string.
-    fn decode(&self, enc: &str) -> Result<Vec<u8>, DecodeError>;
+    /// Decode data.
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError>;
+
+    /// Decode data, and tell whether it was verified by a checksum of the encoded data.
+    /// A checksum which does not match is an error. By default, bases have no checksum.
+    fn decode_verified(&self, enc: &[u8]) -> Result<(Vec<u8>, bool), DecodeError> {
+        self.decode(enc).map(|plain| (plain, false))
+    }
+
+    /// Get a copy of the base configured with the given options, e.g. `url` for base64.
+    /// By default, bases do not support any option.
+    fn with_options(&self, options: &[String]) -> Result<Box<dyn Base>, BaseError> {
+        let name = self.get_metadata().name;
+        match options.first() {
+            Some(option) => Err(BaseError::UnsupportedOption(name, option.clone())),
+            None => get_base_from_name(name),
+        }
+    }
+
+    /// Get the options configuring the base, which are not part of its name.
+    /// By default, bases do not have any option.
+    fn options(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Expand ranges of characters, given by their first and last characters, e.g. `AZaz`.
+fn char_ranges(ranges: &str) -> Vec<char> {
+    let bounds = ranges.chars().collect::<Vec<_>>();
+    bounds
+        .chunks(2)
+        .flat_map(|range| range[0]..=range[1])
+        .collect()
+}
+
+/// Parse hexadecimal digits, unlike `from_str_radix` the sign is not accepted.
+fn parse_hex(digits: &[u8]) -> Option<u32> {
+    if digits.is_empty() {
+        return None;
+    }
+    digits.iter().try_fold(0u32, |value, &digit| {
+        value
+            .checked_mul(16)?
+            .checked_add((digit as char).to_digit(16)?)
+    })
+}
+
+/// Get the code points of data, the bytes which are not UTF-8 being escaped as the lone surrogates
+/// U+DC80 to U+DCFF, as the `surrogateescape` error handler of Python, to encode them reversibly.
+fn code_points(data: &[u8]) -> Vec<u32> {
+    let mut code_points = Vec::with_capacity(data.len());
+    for chunk in data.utf8_chunks() {
+        code_points.extend(chunk.valid().chars().map(u32::from));
+        code_points.extend(chunk.invalid().iter().map(|&b| 0xdc00 | b as u32));
+    }
+    code_points
+}
+
+/// Get the UTF-16 code units of a code point, a lone surrogate being a single unit.
+fn utf16_units(code_point: u32) -> Vec<u16> {
+    match char::from_u32(code_point) {
+        Some(c) => c.encode_utf16(&mut [0; 2]).to_vec(),
+        None => vec![code_point as u16],
+    }
+}
+
+/// Push the UTF-8 encoding of a code point, or the byte escaped by a lone surrogate.
+/// Returns `None` if the code point is neither.
+fn push_code_point(plain: &mut Vec<u8>, code_point: u32) -> Option<()> {
+    match char::from_u32(code_point) {
+        Some(c) => plain.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        None if (0xdc80..=0xdcff).contains(&code_point) => plain.push(code_point as u8),
+        None => return None,
+    }
+    Some(())
+}
+
+/// Push the UTF-8 encoding of UTF-16 code units, the lone surrogates escaping bytes.
+/// Returns `None` if a lone surrogate does not escape a byte.
+fn push_utf16(plain: &mut Vec<u8>, units: &[u16]) -> Option<()> {
+    for c in char::decode_utf16(units.iter().copied()) {
+        let code_point = c.map_or_else(|e| e.unpaired_surrogate() as u32, u32::from);
+        push_code_point(plain, code_point)?;
+    }
+    Some(())
 }
 
 /// Get a list of all defined bases.
@@ -90,12 +194,47 @@ pub fn get_bases() -> Vec<Box<dyn Base>> {
         Box::new(module_base2::Base2),
         Box::new(module_base10::Base10),
         Box::new(module_hex::Hex),
+        Box::new(module_charcode::Charcode::new(10)),
+        Box::new(module_charcode::Charcode::new(8)),
+        Box::new(module_charcode::Charcode::new(16)),
+        Box::new(module_charcode::Charcode::new(2)),
         Box::new(module_base32::Base32),
+        Box::new(module_base32::Base32NoPad),
+        Box::new(module_base32::Base32Hex),
+        Box::new(module_base32::Base32Crockford),
+        Box::new(module_base32::Base32CrockfordCheck),
+        Box::new(module_base32::ZBase32),
         Box::new(module_base36::Base36),
+        Box::new(module_base45::Base45),
         Box::new(module_base58::Base58),
         Box::new(module_base62::Base62),
         Box::new(module_base64::Base64),
+        Box::new(module_base64::Base64Url),
+        Box::new(module_base64::Base64NoPad),
+        Box::new(module_base64::Base64UrlNoPad),
+        Box::new(module_base64::Base64Mime),
+        Box::new(module_base64::Base64Imap),
         Box::new(module_base85::Base85),
+        Box::new(module_base85::Ascii85),
+        Box::new(module_base85::Btoa),
+        Box::new(module_base85::Z85),
+        Box::new(module_base85::Base85Rfc1924),
+        Box::new(module_base91::Base91),
+        Box::new(module_base91::Base92),
+        Box::new(module_base100::Base100),
+        Box::new(module_base2048::Base2048),
+        Box::new(module_base65536::Base65536),
+        Box::new(module_uuencode::Uuencode),
+        Box::new(module_uuencode::UuencodeRaw),
+        Box::new(module_uuencode::Xxencode),
+        Box::new(module_uuencode::XxencodeRaw),
+        Box::new(module_binhex::BinHex),
+        Box::new(module_yenc::YEnc),
+        Box::new(module_url::Url),
+        Box::new(module_html::Html),
+        Box::new(module_quoted_printable::QuotedPrintable),
+        Box::new(module_escape::UnicodeEscape),
+        Box::new(module_escape::HexEscape),
     ]
 }
 
@@ -118,6 +257,9 @@ pub enum BaseError {
     /// The base was not found.
     #[error("Base not found: {0}")]
     NotFound(String),
+    /// The base does not support the option.
+    #[error("Unsupported option for {0}: {1}")]
+    UnsupportedOption(&'static str, String),
 }
 
 /// Get a base from its name or short name.
@@ -142,15 +284,15 @@ mod tests {
     #[test]
     fn test_base64_is_valid() {
         let base = Base64;
-        assert!(base.is_valid(&String::from("YWJj")));
-        assert!(base.is_valid(&String::from("aGVsbG8gd29ybGQ=")));
+        assert!(base.is_valid(b"YWJj"));
+        assert!(base.is_valid(b"aGVsbG8gd29ybGQ="));
     }
 
     #[test]
     fn test_base64_is_not_valid() {
         let base = Base64;
-        assert!(!base.is_valid(&String::from("YW%Jj")));
-        assert!(!base.is_valid(&String::from("aGVsbG8gd29!ybGQ=")));
-        assert!(!base.is_valid(&String::from("aGVsbG8gd29ybGQ=a")));
+        assert!(!base.is_valid(b"YW%Jj"));
+        assert!(!base.is_valid(b"aGVsbG8gd29!ybGQ="));
+        assert!(!base.is_valid(b"aGVsbG8gd29ybGQ=a"));
     }
 }
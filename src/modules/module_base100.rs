@@ -0,0 +1,120 @@
+use std::sync::LazyLock;
+
+/// Base100 module, encoding each byte as an emoji.
+pub struct Base100;
+
+use super::*;
+
+/// Code point of the emoji encoding the byte 0, the other bytes follow.
+const FIRST: u32 = 0x1F3F7;
+
+/// The 256 emojis, in the order of the bytes they encode.
+static ALPHABET: LazyLock<String> = LazyLock::new(|| (0..=255).map(encode_byte).collect());
+
+impl Base for Base100 {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        static METADATA: LazyLock<BaseMetadata> = LazyLock::new(|| BaseMetadata {
+            name: "base100",
+            short_name: "b100",
+            base: ALPHABET.as_str(),
+            padding: None,
+        });
+        &METADATA
+    }
+
+    fn is_valid(&self, encoded: &[u8]) -> bool {
+        std::str::from_utf8(encoded)
+            .is_ok_and(|encoded| encoded.chars().all(|c| decode_char(c).is_some()))
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        plain
+            .iter()
+            .map(|&b| encode_byte(b))
+            .collect::<String>()
+            .into_bytes()
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        std::str::from_utf8(enc)?
+            .char_indices()
+            .map(|(i, c)| decode_char(c).ok_or(DecodeError::InvalidByte(i, enc[i])))
+            .collect()
+    }
+}
+
+/// Get the emoji encoding a byte.
+fn encode_byte(b: u8) -> char {
+    char::from_u32(FIRST + b as u32).unwrap()
+}
+
+/// Get the byte encoded by an emoji.
+fn decode_char(c: char) -> Option<u8> {
+    (c as u32)
+        .checked_sub(FIRST)
+        .and_then(|b| u8::try_from(b).ok())
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let base = Base100;
+
+        const TESTLIST: [(&[u8], &str); 6] = [
+            (b"Hello World!", "🐿👜👣👣👦🐗👎👦👩👣👛🐘"),
+            (b"the quick", "👫👟👜🐗👨👬👠👚👢"),
+            (b"\x7fELF", "👶🐼👃🐽"),
+            (b"", ""),
+            (b"\0", "🏷"),
+            (b"\xff", "📶"),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                base.encode(plaintext),
+                ciphertext.as_bytes(),
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let base = Base100;
+
+        assert_eq!(
+            base.decode("🐿👜a".as_bytes()),
+            Err(DecodeError::InvalidByte(8, b'a'))
+        );
+        assert_eq!(
+            base.decode("🐿📷".as_bytes()),
+            Err(DecodeError::InvalidByte(4, 0xf0))
+        );
+        assert!(matches!(
+            base.decode(b"\xf0\x9f"),
+            Err(DecodeError::InvalidUtf8(_))
+        ));
+    }
+
+    #[test]
+    fn test_is_valid() {
+        let base = Base100;
+
+        assert_eq!(base.get_metadata().base.chars().count(), 256);
+        assert!(base.is_valid("🐿👜👣👣👦".as_bytes()));
+        assert!(!base.is_valid("🐿👜a".as_bytes()));
+        assert!(!base.is_valid(b"\xff"));
+    }
+}
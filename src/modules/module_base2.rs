@@ -15,21 +15,20 @@ impl Base for Base2 {
         }
     }
 
-    fn encode(&self, plain: &[u8]) -> String {
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
         plain
             .iter()
             .map(|&byte| format!("{:08b}", byte))
             .collect::<Vec<String>>()
             .concat()
+            .into_bytes()
     }
 
-    fn decode(&self, enc: &str) -> Result<Vec<u8>, DecodeError> {
-        enc.chars()
-            .collect::<Vec<char>>()
-            .chunks(8)
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        enc.chunks(8)
             .map(|chunk| {
-                let byte_str: String = chunk.iter().collect();
-                u8::from_str_radix(&byte_str, 2).map_err(|_| DecodeError::Error)
+                let byte_str = std::str::from_utf8(chunk)?;
+                u8::from_str_radix(byte_str, 2).map_err(|_| DecodeError::Error)
             })
             .collect::<Result<Vec<u8>, DecodeError>>()
     }
@@ -67,13 +66,13 @@ mod tests {
         for (plaintext, ciphertext) in TESTLIST.iter() {
             assert_eq!(
                 base.encode(plaintext),
-                *ciphertext,
+                ciphertext.as_bytes(),
                 "Encoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
             );
 
             assert_eq!(
-                base.decode(ciphertext).unwrap(),
+                base.decode(ciphertext.as_bytes()).unwrap(),
                 *plaintext,
                 "Decoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Base2048 module, by qntm, encoding 11 bits per character for Twitter.
+/// The last 1 to 3 bits are encoded with one of the 8 tail characters.
+/// The missing bits of the last character are padded with ones.
+pub struct Base2048;
+
+use super::*;
+
+/// Ranges of the 2048 characters encoding 11 bits, as pairs of first and last characters.
+const RANGES: &str = "89AZazÆÆÐÐØØÞßææððøøþþĐđĦħııĸĸŁłŊŋŒœŦŧƀƟƢƮƱǃǝǝǤǥǶǷȜȝȠȥȴʯͰͳͶͷͻͽͿͿΑΡΣΩαωϏϏϗϯϳϳϷϸϺϿЂЂЄІЈЋЏИКикяђђєіјћџѵѸҁҊӀӃӏӔӕӘәӠӡӨөӶӷӺԯԱՖաֆאתװײؠءاؿفي٠٩ٮٯٱٴٹڿہہۃےەەۮۼۿۿܐܐܒܯݍޥޱޱ߀ߪࠀࠕࡀࡘࡠࡪࢠࢴࢶࢽऄनपरलळवहऽऽॐॐॠॡ०९ॲঀঅঌএঐওনপরললশহঽঽৎৎৠৡ০ৱ৴৹ৼৼਅਊਏਐਓਨਪਰਲਲਵਵਸਹੜੜ੦੯ੲੴઅઍએઑઓનપરલળવહઽઽૐૐૠૡ૦૯ૹૹଅଌଏଐଓନପରଲଳଵହଽଽୟୡ୦୯ୱ୷ஃஃஅஊஎஐஒஓஙசஜஜஞடணதநபமஹௐௐ௦௲అఌఎఐఒనపహఽఽౘౚౠౡ౦౯౸౾ಀಀಅಌಎಐಒನಪಳವಹಽಽೞೞೠೡ೦೯ೱೲഅഌഎഐഒഺഽഽൎൎൔൖ൘ൡ൦൸ൺൿඅඖකනඳරලලවෆ෦෯กะาาเๅ๐๙ກຂຄຄງຈຊຊຍຍດທນຟມຣລລວວສຫອະາາຽຽເໄ໐໙ໞໟༀༀ༠༳ཀགངཇཉཌཎདནབམཛཝཨཪཬྈྌကဪဿ၉ၐၕ";
+/// Range of the 8 tail characters encoding the last 3 bits.
+const TAIL_RANGE: &str = "07";
+
+const BITS_PER_CHAR: u32 = 11;
+const BITS_PER_TAIL: u32 = 3;
+
+/// The characters encoding 11 bits, then the tail characters.
+static ALPHABET: LazyLock<Vec<char>> = LazyLock::new(|| {
+    let mut alphabet = char_ranges(RANGES);
+    alphabet.extend(char_ranges(TAIL_RANGE));
+    alphabet
+});
+
+/// Number of bits and value encoded by each character.
+static LOOKUP: LazyLock<HashMap<char, (u32, u32)>> = LazyLock::new(|| {
+    ALPHABET
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| match i.checked_sub(1 << BITS_PER_CHAR) {
+            Some(tail) => (c, (BITS_PER_TAIL, tail as u32)),
+            None => (c, (BITS_PER_CHAR, i as u32)),
+        })
+        .collect()
+});
+
+impl Base for Base2048 {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        static METADATA: LazyLock<BaseMetadata> = LazyLock::new(|| BaseMetadata {
+            name: "base2048",
+            short_name: "b2048",
+            base: ALPHABET.iter().collect::<String>().leak(),
+            padding: None,
+        });
+        &METADATA
+    }
+
+    fn is_valid(&self, encoded: &[u8]) -> bool {
+        std::str::from_utf8(encoded)
+            .is_ok_and(|encoded| encoded.chars().all(|c| LOOKUP.contains_key(&c)))
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let mut enc = String::with_capacity(plain.len() * 8 / 11 * 2 + 3);
+        let (mut bits, mut len) = (0u32, 0);
+        for &byte in plain {
+            bits = bits << 8 | byte as u32;
+            len += 8;
+            if len >= BITS_PER_CHAR {
+                len -= BITS_PER_CHAR;
+                enc.push(ALPHABET[(bits >> len) as usize]);
+                bits &= (1 << len) - 1;
+            }
+        }
+        match len {
+            0 => {}
+            1..=BITS_PER_TAIL => {
+                let padding = BITS_PER_TAIL - len;
+                let value = bits << padding | ((1 << padding) - 1);
+                enc.push(ALPHABET[(1 << BITS_PER_CHAR) + value as usize]);
+            }
+            _ => {
+                let padding = BITS_PER_CHAR - len;
+                enc.push(ALPHABET[(bits << padding | ((1 << padding) - 1)) as usize]);
+            }
+        }
+        enc.into_bytes()
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let enc_str = std::str::from_utf8(enc)?;
+        let mut plain = Vec::with_capacity(enc.len());
+        let (mut bits, mut len) = (0u32, 0);
+        let mut chars = enc_str.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            let &(value_len, value) = LOOKUP.get(&c).ok_or(DecodeError::InvalidByte(i, enc[i]))?;
+            // A tail character can only be the last one.
+            if value_len == BITS_PER_TAIL && chars.peek().is_some() {
+                return Err(DecodeError::InvalidByte(i, enc[i]));
+            }
+
+            bits = bits << value_len | value;
+            len += value_len;
+            while len >= 8 {
+                len -= 8;
+                plain.push((bits >> len) as u8);
+                bits &= (1 << len) - 1;
+            }
+        }
+        if bits != (1 << len) - 1 {
+            return Err(DecodeError::InvalidPadding);
+        }
+        Ok(plain)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alphabet() {
+        assert_eq!(ALPHABET.len(), 2048 + 8);
+        assert_eq!(LOOKUP.len(), 2048 + 8);
+        assert_eq!(LOOKUP[&'8'], (11, 0));
+        assert_eq!(LOOKUP[&'ၕ'], (11, 2047));
+        assert_eq!(LOOKUP[&'0'], (3, 0));
+        assert_eq!(LOOKUP[&'7'], (3, 7));
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let base = Base2048;
+
+        const TESTLIST: [(&[u8], &str); 9] = [
+            (b"hello world", "ڵϠɲඬæஊಣࢷ"),
+            (b"", ""),
+            (b"\0", "F"),
+            (b"\0\0", "8Đ"),
+            (b"\0\0\0", "881"),
+            (b"\xff", "ၕ"),
+            (b"\xff\xe0", "ၕĐ"),
+            (b"\xff\xff\xff", "ၕၕ7"),
+            (b"\0\0\0\0\0\0\0\0\0\0\0", "88888888"),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                String::from_utf8(base.encode(plaintext)).unwrap(),
+                *ciphertext,
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+
+        let data = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(base.decode(&base.encode(&data)).unwrap(), data);
+        assert!(base.is_valid(&base.encode(b"Hello World!")));
+        assert!(!base.is_valid("ၕ!".as_bytes()));
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let base = Base2048;
+
+        assert_eq!(
+            base.decode("8!".as_bytes()),
+            Err(DecodeError::InvalidByte(1, b'!'))
+        );
+        assert_eq!(
+            base.decode("70".as_bytes()),
+            Err(DecodeError::InvalidByte(0, b'7'))
+        );
+        assert_eq!(
+            base.decode("ၕ6".as_bytes()),
+            Err(DecodeError::InvalidPadding)
+        );
+    }
+}
@@ -1,9 +1,35 @@
-/// Base32 module.
+/// Base32 module (RFC4648).
 pub struct Base32;
 
+/// Base32 module without padding.
+pub struct Base32NoPad;
+
+/// Base32 module with the extended hex alphabet (RFC4648), preserving the sort order.
+pub struct Base32Hex;
+
+/// Crockford's base32 module, avoiding the ambiguous letters.
+/// When decoding, the hyphens are ignored, `I` and `L` are read as `1` and `O` as `0`.
+/// The `check` option appends a check symbol.
+pub struct Base32Crockford;
+
+/// Crockford's base32 module with a check symbol: the data read as a big-endian number, modulo 37.
+pub struct Base32CrockfordCheck;
+
+/// z-base-32 module, designed to be easier to read and type for humans.
+pub struct ZBase32;
+
 use super::*;
 
-const ALPHABET: base32::Alphabet = base32::Alphabet::RFC4648 { padding: true };
+const RFC4648: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const RFC4648_HEX: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const Z_BASE_32: &[u8; 32] = b"ybndrfg8ejkmcpqxot1uwisza345h769";
+
+/// Check symbols of Crockford's base32, for the values 32 to 36.
+const CROCKFORD_CHECK: &[u8; 5] = b"*~$=U";
+
+/// Marks the bytes which are not in an alphabet, in a decoding table.
+const INVALID: u8 = 0xff;
 
 impl Base for Base32 {
     fn get_metadata(&self) -> &'static BaseMetadata {
@@ -15,13 +41,230 @@ impl Base for Base32 {
         }
     }
 
-    fn encode(&self, plain: &[u8]) -> String {
-        base32::encode(ALPHABET, plain)
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        encode_with(RFC4648, true, plain)
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_with(&decode_table(RFC4648), true, enc)
+    }
+}
+
+impl Base for Base32NoPad {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base32-nopad",
+            short_name: "b32np",
+            base: "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        encode_with(RFC4648, false, plain)
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_with(&decode_table(RFC4648), false, enc)
+    }
+}
+
+impl Base for Base32Hex {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base32hex",
+            short_name: "b32h",
+            base: "0123456789ABCDEFGHIJKLMNOPQRSTUV",
+            padding: Some("="),
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        encode_with(RFC4648_HEX, true, plain)
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_with(&decode_table(RFC4648_HEX), true, enc)
+    }
+}
+
+impl Base for Base32Crockford {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base32-crockford",
+            short_name: "b32c",
+            base: "0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        encode_with(CROCKFORD, false, plain)
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let enc = enc
+            .iter()
+            .copied()
+            .filter(|&b| b != b'-')
+            .collect::<Vec<_>>();
+        decode_with(&crockford_table(), false, &enc)
+    }
+
+    fn with_options(&self, options: &[String]) -> Result<Box<dyn Base>, BaseError> {
+        match options {
+            [] => Ok(Box::new(Base32Crockford)),
+            [option] if option == "check" => Ok(Box::new(Base32CrockfordCheck)),
+            [.., option] => Err(BaseError::UnsupportedOption(
+                self.get_metadata().name,
+                option.clone(),
+            )),
+        }
+    }
+}
+
+impl Base for Base32CrockfordCheck {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base32-crockford-check",
+            short_name: "b32cc",
+            base: "0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let mut enc = encode_with(CROCKFORD, false, plain);
+        let check = crockford_check(plain);
+        enc.push(match CROCKFORD.get(check) {
+            Some(&symbol) => symbol,
+            None => CROCKFORD_CHECK[check - 32],
+        });
+        enc
     }
 
-    fn decode(&self, enc: &str) -> Result<Vec<u8>, DecodeError> {
-        base32::decode(ALPHABET, enc).ok_or(DecodeError::Error)
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let enc = enc
+            .iter()
+            .copied()
+            .filter(|&b| b != b'-')
+            .collect::<Vec<_>>();
+        let (&symbol, enc) = enc.split_last().ok_or(DecodeError::InvalidLength)?;
+
+        let table = crockford_table();
+        let check = match table[symbol as usize] {
+            INVALID => CROCKFORD_CHECK
+                .iter()
+                .position(|c| c.eq_ignore_ascii_case(&symbol))
+                .map(|i| i + 32)
+                .ok_or(DecodeError::InvalidByte(enc.len(), symbol))?,
+            value => value as usize,
+        };
+
+        let plain = decode_with(&table, false, enc)?;
+        if crockford_check(&plain) != check {
+            return Err(DecodeError::Error);
+        }
+        Ok(plain)
+    }
+}
+
+impl Base for ZBase32 {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "z-base-32",
+            short_name: "zb32",
+            base: "ybndrfg8ejkmcpqxot1uwisza345h769",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        encode_with(Z_BASE_32, false, plain)
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_with(&decode_table(Z_BASE_32), false, enc)
+    }
+}
+
+/// Encode data with the given alphabet, by groups of 5 bytes giving 8 symbols.
+fn encode_with(alphabet: &[u8; 32], padding: bool, plain: &[u8]) -> Vec<u8> {
+    let mut enc = Vec::with_capacity(plain.len().div_ceil(5) * 8);
+    for chunk in plain.chunks(5) {
+        let mut group = [0; 8];
+        group[3..3 + chunk.len()].copy_from_slice(chunk);
+        let bits = u64::from_be_bytes(group);
+
+        let symbols = (chunk.len() * 8).div_ceil(5);
+        for i in 0..symbols {
+            enc.push(alphabet[(bits >> (35 - 5 * i) & 0x1f) as usize]);
+        }
+        if padding {
+            enc.resize(enc.len() + 8 - symbols, b'=');
+        }
+    }
+    enc
+}
+
+/// Get the decoding table of an alphabet: the value of each byte, whatever its case.
+fn decode_table(alphabet: &[u8; 32]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    for (value, &symbol) in alphabet.iter().enumerate() {
+        table[symbol.to_ascii_uppercase() as usize] = value as u8;
+        table[symbol.to_ascii_lowercase() as usize] = value as u8;
     }
+    table
+}
+
+/// Get the decoding table of Crockford's base32, reading the ambiguous letters as digits.
+fn crockford_table() -> [u8; 256] {
+    let mut table = decode_table(CROCKFORD);
+    for (alias, value) in [(b'I', 1), (b'L', 1), (b'O', 0)] {
+        table[alias as usize] = value;
+        table[alias.to_ascii_lowercase() as usize] = value;
+    }
+    table
+}
+
+/// Decode data with the given decoding table, checking the length and the padding.
+fn decode_with(table: &[u8; 256], padding: bool, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    std::str::from_utf8(enc)?;
+    let data = if padding {
+        if !enc.len().is_multiple_of(8) {
+            return Err(DecodeError::InvalidLength);
+        }
+        let padding_len = enc.iter().rev().take(6).take_while(|&&b| b == b'=').count();
+        &enc[..enc.len() - padding_len]
+    } else {
+        enc
+    };
+    match data.len() % 8 {
+        0 | 2 | 4 | 5 | 7 => {}
+        _ if padding => return Err(DecodeError::InvalidPadding),
+        _ => return Err(DecodeError::InvalidLength),
+    }
+
+    let mut plain = Vec::with_capacity(data.len() * 5 / 8);
+    for (i, chunk) in data.chunks(8).enumerate() {
+        let mut bits = 0u64;
+        for (j, &symbol) in chunk.iter().enumerate() {
+            let value = table[symbol as usize];
+            if value == INVALID {
+                return Err(DecodeError::InvalidByte(i * 8 + j, symbol));
+            }
+            bits |= (value as u64) << (35 - 5 * j);
+        }
+        plain.extend_from_slice(&bits.to_be_bytes()[3..3 + chunk.len() * 5 / 8]);
+    }
+    Ok(plain)
+}
+
+/// Get the check value of Crockford's base32: the data read as a big-endian number, modulo 37.
+fn crockford_check(plain: &[u8]) -> usize {
+    plain
+        .iter()
+        .fold(0, |check, &byte| (check * 256 + byte as usize) % 37)
 }
 
 #[cfg(test)]
@@ -50,17 +293,80 @@ mod tests {
         for (plaintext, ciphertext) in TESTLIST.iter() {
             assert_eq!(
                 base.encode(plaintext),
-                *ciphertext,
+                ciphertext.as_bytes(),
                 "Encoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
             );
 
             assert_eq!(
-                base.decode(ciphertext).unwrap(),
+                base.decode(ciphertext.as_bytes()).unwrap(),
                 *plaintext,
                 "Decoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
             );
         }
     }
+
+    #[test]
+    fn test_invalid() {
+        let base = Base32;
+        assert_eq!(base.decode(b"jbswy3dp").unwrap(), b"Hello");
+        assert_eq!(base.decode(b"MFQQ"), Err(DecodeError::InvalidLength));
+        assert_eq!(base.decode(b"MFQ====="), Err(DecodeError::InvalidPadding));
+        assert_eq!(base.decode(b"ME======"), Ok(b"a".to_vec()));
+        assert_eq!(
+            base.decode(b"M=Q=C==="),
+            Err(DecodeError::InvalidByte(1, b'='))
+        );
+        assert_eq!(
+            base.decode(b"MFQWCYL1"),
+            Err(DecodeError::InvalidByte(7, b'1'))
+        );
+    }
+
+    #[test]
+    fn test_variants() {
+        // Test vectors of RFC4648.
+        assert_eq!(Base32NoPad.encode(b"foob"), b"MZXW6YQ");
+        assert_eq!(Base32NoPad.decode(b"mzxw6yq").unwrap(), b"foob");
+        assert!(Base32NoPad.decode(b"MZXW6YQ=").is_err());
+        assert_eq!(Base32Hex.encode(b"foob"), b"CPNMUOG=");
+        assert_eq!(Base32Hex.decode(b"cpnmuog=").unwrap(), b"foob");
+        assert_eq!(Base32Hex.encode(b"foobar"), b"CPNMUOJ1E8======");
+
+        assert_eq!(ZBase32.encode(b"\xf0\xbf\xc7"), b"6n9hq");
+        assert_eq!(ZBase32.decode(b"6n9hq").unwrap(), b"\xf0\xbf\xc7");
+        assert_eq!(ZBase32.decode(b"6N9HQ").unwrap(), b"\xf0\xbf\xc7");
+    }
+
+    #[test]
+    fn test_crockford() {
+        let base = Base32Crockford;
+        assert_eq!(base.encode(b"Hello"), b"91JPRV3F");
+        assert_eq!(base.decode(b"91JPRV3F").unwrap(), b"Hello");
+        assert_eq!(base.decode(b"9ljprv3f").unwrap(), b"Hello");
+        assert_eq!(base.decode(b"91JP-RV3F").unwrap(), b"Hello");
+        assert_eq!(base.decode(b"0O").unwrap(), b"\0");
+        assert!(base.decode(b"91JPRV3U").is_err());
+
+        let base = Base32Crockford
+            .with_options(&["check".to_string()])
+            .unwrap();
+        assert_eq!(base.get_metadata().name, "base32-crockford-check");
+        // "Hello" is 310939249775 = 37 * 8403763507 + 16.
+        assert_eq!(base.encode(b"Hello"), b"91JPRV3FG");
+        assert_eq!(base.decode(b"91JPRV3FG").unwrap(), b"Hello");
+        assert_eq!(base.decode(b"91JPRV3Fg").unwrap(), b"Hello");
+        assert_eq!(base.decode(b"91JPRV3FH"), Err(DecodeError::Error));
+        assert_eq!(base.encode(b"\x20"), b"40*");
+        assert_eq!(base.decode(b"40*").unwrap(), b"\x20");
+        assert_eq!(base.decode(b""), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            Base32Crockford.with_options(&["nope".to_string()]).err(),
+            Some(BaseError::UnsupportedOption(
+                "base32-crockford",
+                "nope".to_string()
+            ))
+        );
+    }
 }
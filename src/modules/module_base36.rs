@@ -15,12 +15,12 @@ impl Base for Base36 {
         }
     }
 
-    fn encode(&self, plain: &[u8]) -> String {
-        base_x::encode(ALPHABET, plain)
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        base_x::encode(ALPHABET, plain).into_bytes()
     }
 
-    fn decode(&self, enc: &str) -> Result<Vec<u8>, DecodeError> {
-        base_x::decode(ALPHABET, enc).map_err(|_| DecodeError::Error)
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        base_x::decode(ALPHABET, std::str::from_utf8(enc)?).map_err(|_| DecodeError::Error)
     }
 }
 
@@ -50,13 +50,13 @@ mod tests {
         for (plaintext, ciphertext) in TESTLIST.iter() {
             assert_eq!(
                 base.encode(plaintext),
-                *ciphertext,
+                ciphertext.as_bytes(),
                 "Encoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
             );
 
             assert_eq!(
-                base.decode(ciphertext).unwrap(),
+                base.decode(ciphertext.as_bytes()).unwrap(),
                 *plaintext,
                 "Decoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
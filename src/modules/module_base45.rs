@@ -0,0 +1,118 @@
+/// Base45 module (RFC 9285), used in QR codes.
+pub struct Base45;
+
+use super::*;
+
+const ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+impl Base for Base45 {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base45",
+            short_name: "b45",
+            base: "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let mut enc = Vec::with_capacity(plain.len().div_ceil(2) * 3);
+        for pair in plain.chunks(2) {
+            // Each pair of bytes gives 3 characters, the least significant first.
+            let mut value = pair.iter().fold(0, |acc, &b| acc * 256 + b as usize);
+            for _ in 0..pair.len() + 1 {
+                enc.push(ALPHABET[value % 45]);
+                value /= 45;
+            }
+        }
+        enc
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        if enc.len() % 3 == 1 {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        let mut plain = Vec::with_capacity(enc.len() / 3 * 2 + 1);
+        for (i, group) in enc.chunks(3).enumerate() {
+            let digits = group
+                .iter()
+                .enumerate()
+                .map(|(j, &c)| {
+                    ALPHABET
+                        .iter()
+                        .position(|&a| a == c)
+                        .ok_or(DecodeError::InvalidByte(i * 3 + j, c))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let value = digits.iter().rev().fold(0, |acc, digit| acc * 45 + digit);
+            // A group must not overflow the bytes it encodes.
+            match group.len() {
+                3 => plain.extend_from_slice(
+                    &u16::try_from(value)
+                        .map_err(|_| DecodeError::Error)?
+                        .to_be_bytes(),
+                ),
+                _ => plain.push(u8::try_from(value).map_err(|_| DecodeError::Error)?),
+            }
+        }
+        Ok(plain)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let base = Base45;
+
+        const TESTLIST: [(&[u8], &str); 10] = [
+            (b"Hello World!", "%69 VD82EI2B.KESTC"),
+            (b"AB", "BB8"),
+            (b"Hello!!", "%69 VD92EX0"),
+            (b"base-45", "UJCLQE7W581"),
+            (b"ietf!", "QED8WEX0"),
+            (b"", ""),
+            (b"a", "72"),
+            (b"aa", ":DC"),
+            (b"aaa", ":DC72"),
+            (b"\xff\xff", "FGW"),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                base.encode(plaintext),
+                ciphertext.as_bytes(),
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let base = Base45;
+
+        assert_eq!(base.decode(b"BB8B"), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            base.decode(b"BB8b2"),
+            Err(DecodeError::InvalidByte(3, b'b'))
+        );
+        assert_eq!(
+            base.decode(b"BB8b,2"),
+            Err(DecodeError::InvalidByte(3, b'b'))
+        );
+        assert_eq!(base.decode(b"GGW"), Err(DecodeError::Error));
+        assert_eq!(base.decode(b"::"), Err(DecodeError::Error));
+    }
+}
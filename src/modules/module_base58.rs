@@ -15,17 +15,19 @@ impl Base for Base58 {
         }
     }
 
-    fn encode(&self, plain: &[u8]) -> String {
-        plain.to_base58()
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        plain.to_base58().into_bytes()
     }
 
-    fn decode(&self, enc: &str) -> Result<Vec<u8>, DecodeError> {
-        enc.from_base58().map_err(|e| match e {
-            base58::FromBase58Error::InvalidBase58Character(c, n) => {
-                DecodeError::InvalidByte(n, c as u8)
-            }
-            base58::FromBase58Error::InvalidBase58Length => DecodeError::InvalidLength,
-        })
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        std::str::from_utf8(enc)?
+            .from_base58()
+            .map_err(|e| match e {
+                base58::FromBase58Error::InvalidBase58Character(c, n) => {
+                    DecodeError::InvalidByte(n, c as u8)
+                }
+                base58::FromBase58Error::InvalidBase58Length => DecodeError::InvalidLength,
+            })
     }
 }
 
@@ -55,13 +57,13 @@ mod tests {
         for (plaintext, ciphertext) in TESTLIST.iter() {
             assert_eq!(
                 base.encode(plaintext),
-                *ciphertext,
+                ciphertext.as_bytes(),
                 "Encoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
             );
 
             assert_eq!(
-                base.decode(ciphertext).unwrap(),
+                base.decode(ciphertext.as_bytes()).unwrap(),
                 *plaintext,
                 "Decoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
@@ -13,15 +13,15 @@ impl Base for Base62 {
         }
     }
 
-    fn encode(&self, plain: &[u8]) -> String {
-        bs62::encode_data(plain)
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        bs62::encode_data(plain).into_bytes()
     }
 
-    fn decode(&self, enc: &str) -> Result<Vec<u8>, DecodeError> {
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
         if enc.is_empty() {
             return Ok(vec![]);
         }
-        bs62::decode_data_forgiving(enc).map_err(|_| DecodeError::Error)
+        bs62::decode_data_forgiving(std::str::from_utf8(enc)?).map_err(|_| DecodeError::Error)
     }
 }
 
@@ -51,13 +51,13 @@ mod tests {
         for (plaintext, ciphertext) in TESTLIST.iter() {
             assert_eq!(
                 base.encode(plaintext),
-                *ciphertext,
+                ciphertext.as_bytes(),
                 "Encoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
             );
 
             assert_eq!(
-                base.decode(ciphertext).unwrap(),
+                base.decode(ciphertext.as_bytes()).unwrap(),
                 *plaintext,
                 "Decoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
@@ -84,7 +84,7 @@ mod tests {
 
         for (plaintext, ciphertext) in TESTLIST.iter() {
             assert_eq!(
-                base.decode(ciphertext).unwrap(),
+                base.decode(ciphertext.as_bytes()).unwrap(),
                 *plaintext,
                 "Decoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
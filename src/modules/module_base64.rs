@@ -1,34 +1,300 @@
-use base64::{engine::general_purpose, Engine};
+use base64::{
+    alphabet,
+    engine::{general_purpose, GeneralPurpose},
+    Engine,
+};
 
 /// Base64 module.
+/// The `url` option selects the URL-safe alphabet, and the `nopad` option removes the padding.
 pub struct Base64;
 
+/// Base64 module with the URL-safe alphabet, e.g. in JWTs.
+pub struct Base64Url;
+
+/// Base64 module without padding, the padded data is rejected when decoding.
+pub struct Base64NoPad;
+
+/// Base64 module with the URL-safe alphabet and without padding.
+pub struct Base64UrlNoPad;
+
+/// Base64 module for MIME, with CRLF line breaks every 76 characters.
+/// Only the data split into several lines is decoded, a single line being base64.
+pub struct Base64Mime;
+
+/// IMAP modified UTF-7 module (RFC 3501), used in mailbox names.
+/// The non printable ASCII characters are encoded in UTF-16 with a modified base64, between `&`
+/// and `-`. The bytes which are not UTF-8 are encoded as lone surrogates.
+/// Only the data with at least one `&` sequence is decoded, as any other text would be unchanged.
+pub struct Base64Imap;
+
 use super::*;
 
+const STANDARD_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Maximum length of a MIME line, without the line break.
+const MIME_LINE_LEN: usize = 76;
+
+const IMAP: GeneralPurpose = GeneralPurpose::new(&alphabet::IMAP_MUTF7, general_purpose::NO_PAD);
+
 impl Base for Base64 {
     fn get_metadata(&self) -> &'static BaseMetadata {
         &BaseMetadata {
             name: "base64",
             short_name: "b64",
-            base: "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+            base: STANDARD_ALPHABET,
             padding: Some("="),
         }
     }
 
-    fn encode(&self, plain: &[u8]) -> String {
-        general_purpose::STANDARD.encode(plain)
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        general_purpose::STANDARD.encode(plain).into_bytes()
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_with(&general_purpose::STANDARD, enc)
     }
 
-    fn decode(&self, enc: &str) -> Result<Vec<u8>, DecodeError> {
-        general_purpose::STANDARD.decode(enc).map_err(|e| match e {
-            base64::DecodeError::InvalidByte(n, c) => DecodeError::InvalidByte(n, c),
-            base64::DecodeError::InvalidLength => DecodeError::InvalidLength,
-            base64::DecodeError::InvalidLastSymbol(_, _) => DecodeError::Error,
-            base64::DecodeError::InvalidPadding => DecodeError::InvalidPadding,
+    fn with_options(&self, options: &[String]) -> Result<Box<dyn Base>, BaseError> {
+        let (mut url, mut padding) = (false, true);
+        for option in options {
+            match option.as_str() {
+                "url" => url = true,
+                "nopad" => padding = false,
+                _ => {
+                    return Err(BaseError::UnsupportedOption(
+                        self.get_metadata().name,
+                        option.clone(),
+                    ))
+                }
+            }
+        }
+
+        Ok(match (url, padding) {
+            (false, true) => Box::new(Base64),
+            (false, false) => Box::new(Base64NoPad),
+            (true, true) => Box::new(Base64Url),
+            (true, false) => Box::new(Base64UrlNoPad),
         })
     }
 }
 
+impl Base for Base64Url {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base64url",
+            short_name: "b64u",
+            base: URL_SAFE_ALPHABET,
+            padding: Some("="),
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        general_purpose::URL_SAFE.encode(plain).into_bytes()
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_with(&general_purpose::URL_SAFE, enc)
+    }
+}
+
+impl Base for Base64NoPad {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base64-nopad",
+            short_name: "b64np",
+            base: STANDARD_ALPHABET,
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        general_purpose::STANDARD_NO_PAD.encode(plain).into_bytes()
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_with(&general_purpose::STANDARD_NO_PAD, enc)
+    }
+}
+
+impl Base for Base64UrlNoPad {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base64url-nopad",
+            short_name: "b64unp",
+            base: URL_SAFE_ALPHABET,
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        general_purpose::URL_SAFE_NO_PAD.encode(plain).into_bytes()
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_with(&general_purpose::URL_SAFE_NO_PAD, enc)
+    }
+}
+
+impl Base for Base64Mime {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base64-mime",
+            short_name: "b64m",
+            base: STANDARD_ALPHABET,
+            padding: Some("="),
+        }
+    }
+
+    fn is_valid(&self, encoded: &[u8]) -> bool {
+        self.decode(encoded).is_ok()
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        general_purpose::STANDARD
+            .encode(plain)
+            .into_bytes()
+            .chunks(MIME_LINE_LEN)
+            .collect::<Vec<_>>()
+            .join(&b"\r\n"[..])
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        // The lines are split by CRLF, or LF as converted by some mail clients.
+        let mut lines = enc
+            .split(|&b| b == b'\n')
+            .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+            .collect::<Vec<_>>();
+        if lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let Some((last, full)) = lines.split_last() else {
+            return Err(DecodeError::InvalidLength);
+        };
+        if full.is_empty()
+            || full.iter().any(|line| line.len() != MIME_LINE_LEN)
+            || last.len() > MIME_LINE_LEN
+        {
+            return Err(DecodeError::InvalidLength);
+        }
+        decode_with(&general_purpose::STANDARD, enc)
+    }
+}
+
+impl Base for Base64Imap {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base64-imap",
+            short_name: "b64imap",
+            base: " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let mut enc = Vec::with_capacity(plain.len());
+        let mut shifted = Vec::new();
+        for code_point in code_points(plain) {
+            match u8::try_from(code_point) {
+                Ok(b) if b == b' ' || b.is_ascii_graphic() => {
+                    if !shifted.is_empty() {
+                        enc.extend(encode_imap_shift(&shifted));
+                        shifted.clear();
+                    }
+                    enc.push(b);
+                    if b == b'&' {
+                        enc.push(b'-');
+                    }
+                }
+                _ => shifted.extend(utf16_units(code_point)),
+            }
+        }
+        if !shifted.is_empty() {
+            enc.extend(encode_imap_shift(&shifted));
+        }
+        enc
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        // Without any `&` sequence, the data is not encoded.
+        if !enc.contains(&b'&') {
+            return Err(DecodeError::Error);
+        }
+        let mut plain = Vec::with_capacity(enc.len());
+        let mut i = 0;
+        while i < enc.len() {
+            match enc[i] {
+                b'&' => {
+                    let len = enc[i + 1..]
+                        .iter()
+                        .position(|&b| b == b'-')
+                        .ok_or(DecodeError::InvalidPadding)?;
+                    let shifted = &enc[i + 1..i + 1 + len];
+                    if shifted.is_empty() {
+                        plain.push(b'&');
+                    } else {
+                        let units = decode_imap_shift(shifted, i + 1)?;
+                        push_utf16(&mut plain, &units).ok_or(DecodeError::InvalidByte(i, b'&'))?;
+                    }
+                    i += len + 2;
+                }
+                b if b == b' ' || b.is_ascii_graphic() => {
+                    plain.push(b);
+                    i += 1;
+                }
+                b => return Err(DecodeError::InvalidByte(i, b)),
+            }
+        }
+        Ok(plain)
+    }
+}
+
+/// Encode UTF-16 code units with the IMAP modified base64, between `&` and `-`.
+fn encode_imap_shift(units: &[u16]) -> Vec<u8> {
+    let utf16 = units
+        .iter()
+        .flat_map(|unit| unit.to_be_bytes())
+        .collect::<Vec<_>>();
+    format!("&{}-", IMAP.encode(utf16)).into_bytes()
+}
+
+/// Decode UTF-16 code units encoded with the IMAP modified base64, found at the given offset.
+fn decode_imap_shift(shifted: &[u8], offset: usize) -> Result<Vec<u16>, DecodeError> {
+    let utf16 = IMAP
+        .decode(shifted)
+        .map_err(|e| convert_error(e, |n| offset + n))?;
+    if utf16.len() % 2 != 0 {
+        return Err(DecodeError::InvalidLength);
+    }
+    Ok(utf16
+        .chunks(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+        .collect())
+}
+
+/// Decode data with the given engine, ignoring the whitespaces, e.g. line breaks.
+fn decode_with(engine: &GeneralPurpose, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    // The offsets of the other bytes in the input, to locate the invalid bytes.
+    let (offsets, enc): (Vec<usize>, Vec<u8>) = enc
+        .iter()
+        .enumerate()
+        .filter(|(_, b)| !b.is_ascii_whitespace())
+        .unzip();
+    engine
+        .decode(enc)
+        .map_err(|e| convert_error(e, |n| offsets[n]))
+}
+
+/// Convert an error of the base64 crate, the offsets of the invalid bytes being mapped to the input.
+fn convert_error(e: base64::DecodeError, offset: impl Fn(usize) -> usize) -> DecodeError {
+    match e {
+        base64::DecodeError::InvalidByte(n, c) => DecodeError::InvalidByte(offset(n), c),
+        base64::DecodeError::InvalidLength => DecodeError::InvalidLength,
+        base64::DecodeError::InvalidLastSymbol(_, _) => DecodeError::Error,
+        base64::DecodeError::InvalidPadding => DecodeError::InvalidPadding,
+    }
+}
+
 #[cfg(test)]
 #[cfg(not(tarpaulin_include))]
 
@@ -55,17 +321,124 @@ mod tests {
         for (plaintext, ciphertext) in TESTLIST.iter() {
             assert_eq!(
                 base.encode(plaintext),
-                *ciphertext,
+                ciphertext.as_bytes(),
                 "Encoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
             );
 
             assert_eq!(
-                base.decode(ciphertext).unwrap(),
+                base.decode(ciphertext.as_bytes()).unwrap(),
                 *plaintext,
                 "Decoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
             );
         }
     }
+
+    #[test]
+    fn test_whitespaces() {
+        assert_eq!(
+            Base64.decode(b" SGVs\nbG8g\r\nV29y bGQh\t").unwrap(),
+            b"Hello World!"
+        );
+        assert_eq!(Base64Url.decode(b"-_8\n=").unwrap(), b"\xfb\xff");
+        assert_eq!(
+            Base64.decode(b"SG Vs\nb!G8"),
+            Err(DecodeError::InvalidByte(7, b'!'))
+        );
+    }
+
+    #[test]
+    fn test_options() {
+        let options = |options: &[&str]| options.iter().map(|o| o.to_string()).collect::<Vec<_>>();
+
+        let base = Base64.with_options(&options(&["url", "nopad"])).unwrap();
+        assert_eq!(base.get_metadata().name, "base64url-nopad");
+        assert_eq!(base.encode(b"\xfb\xff"), b"-_8");
+        assert_eq!(base.decode(b"-_8").unwrap(), b"\xfb\xff");
+        // The padded data is base64url.
+        assert!(base.decode(b"-_8=").is_err());
+
+        let base = Base64.with_options(&options(&["url"])).unwrap();
+        assert_eq!(base.get_metadata().name, "base64url");
+        assert_eq!(base.encode(b"\xfb\xff"), b"-_8=");
+        assert!(base.decode(b"+/8=").is_err());
+
+        let base = Base64.with_options(&options(&["nopad"])).unwrap();
+        assert_eq!(base.get_metadata().name, "base64-nopad");
+        assert_eq!(base.encode(b"a"), b"YQ");
+        assert!(base.decode(b"YQ==").is_err());
+
+        let base = Base64.with_options(&[]).unwrap();
+        assert_eq!(base.get_metadata().name, "base64");
+        assert_eq!(
+            Base64.with_options(&options(&["nope"])).err(),
+            Some(BaseError::UnsupportedOption("base64", "nope".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_mime() {
+        let base = Base64Mime;
+        let plain = [b'a'; 100];
+        let enc = base.encode(&plain);
+        let lines = enc.split(|&b| b == b'\n').collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), MIME_LINE_LEN + 1);
+        assert!(lines[0].ends_with(b"\r"));
+        assert!(base.is_valid(&enc));
+        assert_eq!(base.decode(&enc).unwrap(), plain);
+        let lf = String::from_utf8(enc.clone())
+            .unwrap()
+            .replace("\r\n", "\n");
+        assert_eq!(base.decode(lf.as_bytes()).unwrap(), plain);
+        assert_eq!(base.encode(b"aaa"), b"YWFh");
+
+        // A single line is base64, and the lines must be full.
+        assert_eq!(base.decode(b"YWFh"), Err(DecodeError::InvalidLength));
+        assert_eq!(base.decode(b"YWFh\r\n"), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            base.decode(b"YWFh\r\nYWFh"),
+            Err(DecodeError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_imap() {
+        let base = Base64Imap;
+
+        const TESTLIST: [(&[u8], &str); 5] = [
+            ("Entwürfe".as_bytes(), "Entw&APw-rfe"),
+            (b"Tom & Jerry", "Tom &- Jerry"),
+            (
+                "~peter/mail/台北/日本語".as_bytes(),
+                "~peter/mail/&U,BTFw-/&ZeVnLIqe-",
+            ),
+            ("😀".as_bytes(), "&2D3eAA-"),
+            (b"\x00\xffA\xc3", "&AADc,w-A&3MM-"),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(base.encode(plaintext), ciphertext.as_bytes());
+            assert_eq!(base.decode(ciphertext.as_bytes()).unwrap(), *plaintext);
+        }
+
+        let data = (0..=255).cycle().take(1000).collect::<Vec<u8>>();
+        assert_eq!(base.decode(&base.encode(&data)).unwrap(), data);
+
+        // The text without any `&` sequence is not encoded.
+        assert_eq!(base.encode(b"INBOX"), b"INBOX");
+        assert_eq!(base.decode(b"INBOX"), Err(DecodeError::Error));
+
+        assert_eq!(base.decode(b"&U,BTFw"), Err(DecodeError::InvalidPadding));
+        assert_eq!(
+            base.decode(b"a&U,B!-"),
+            Err(DecodeError::InvalidByte(5, b'!'))
+        );
+        assert_eq!(
+            base.decode(b"a&-\nb"),
+            Err(DecodeError::InvalidByte(3, b'\n'))
+        );
+        assert_eq!(base.decode(b"&AA-"), Err(DecodeError::InvalidLength));
+    }
 }
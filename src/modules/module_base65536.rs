@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Base65536 module, by qntm, encoding 2 bytes per character.
+/// The second byte selects one of 256 blocks of 256 characters, and the first byte the character
+/// in the block. A last single byte is encoded in a distinct block.
+pub struct Base65536;
+
+use super::*;
+
+/// Ranges of the 256 blocks, as pairs of first and last characters.
+const RANGES: &str = "㐀䳿一黿ꄀꏿꔀꗿ𐘀𐛿𒀀𒋿𓀀𓏿𔐀𔗿𖠀𖧿𠀀𨗿";
+/// Block of the last single byte.
+const TAIL_BLOCK: u32 = 0x1500;
+
+/// First code point of each block, in the order of the second byte.
+static BLOCKS: LazyLock<Vec<u32>> = LazyLock::new(|| {
+    char_ranges(RANGES)
+        .into_iter()
+        .map(|c| c as u32)
+        .filter(|c| c & 0xff == 0)
+        .collect()
+});
+
+/// Second byte selecting each block, by first code point of the block.
+static BLOCK_BYTES: LazyLock<HashMap<u32, u8>> = LazyLock::new(|| {
+    BLOCKS
+        .iter()
+        .enumerate()
+        .map(|(b, &block)| (block, b as u8))
+        .collect()
+});
+
+impl Base for Base65536 {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        static METADATA: LazyLock<BaseMetadata> = LazyLock::new(|| BaseMetadata {
+            name: "base65536",
+            short_name: "b65536",
+            base: BLOCKS
+                .iter()
+                .chain(&[TAIL_BLOCK])
+                .flat_map(|&block| block..block + 0x100)
+                .filter_map(char::from_u32)
+                .collect::<String>()
+                .leak(),
+            padding: None,
+        });
+        &METADATA
+    }
+
+    fn is_valid(&self, encoded: &[u8]) -> bool {
+        std::str::from_utf8(encoded).is_ok_and(|encoded| {
+            encoded.chars().all(|c| {
+                let block = c as u32 & !0xff;
+                block == TAIL_BLOCK || BLOCK_BYTES.contains_key(&block)
+            })
+        })
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        plain
+            .chunks(2)
+            .map(|pair| {
+                let block = match pair.get(1) {
+                    Some(&b) => BLOCKS[b as usize],
+                    None => TAIL_BLOCK,
+                };
+                char::from_u32(block + pair[0] as u32).unwrap()
+            })
+            .collect::<String>()
+            .into_bytes()
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let enc_str = std::str::from_utf8(enc)?;
+        let mut plain = Vec::with_capacity(enc_str.len() / 2);
+        let mut chars = enc_str.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            let (block, b) = (c as u32 & !0xff, c as u32 as u8);
+            plain.push(b);
+            if let Some(&b) = BLOCK_BYTES.get(&block) {
+                plain.push(b);
+            } else if block != TAIL_BLOCK || chars.peek().is_some() {
+                // The block of a single byte can only be the last one.
+                return Err(DecodeError::InvalidByte(i, enc[i]));
+            }
+        }
+        Ok(plain)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(BLOCKS.len(), 256);
+        assert_eq!(BLOCKS[0], 0x3400);
+        assert_eq!(BLOCKS[255], 0x28500);
+        assert_eq!(
+            Base65536.get_metadata().base.chars().count(),
+            256 * 256 + 256
+        );
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let base = Base65536;
+
+        const TESTLIST: [(&[u8], &str); 6] = [
+            (b"hello world", "驨ꍬ啯𒁷ꍲᕤ"),
+            (b"", ""),
+            (b"\0", "ᔀ"),
+            (b"\0\0", "㐀"),
+            (b"\xff", "ᗿ"),
+            (b"\xff\xff", "𨗿"),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                String::from_utf8(base.encode(plaintext)).unwrap(),
+                *ciphertext,
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+
+        let data = (0..=255).chain(0..=255).rev().collect::<Vec<u8>>();
+        assert_eq!(base.decode(&base.encode(&data)).unwrap(), data);
+        assert!(base.is_valid("驨ꍬ啯𒁷ꍲᕤ".as_bytes()));
+        assert!(!base.is_valid("驨a".as_bytes()));
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let base = Base65536;
+
+        assert_eq!(
+            base.decode("驨a".as_bytes()),
+            Err(DecodeError::InvalidByte(3, b'a'))
+        );
+        assert_eq!(
+            base.decode("ᕤ驨".as_bytes()),
+            Err(DecodeError::InvalidByte(0, 0xe1))
+        );
+    }
+}
@@ -1,27 +1,309 @@
-/// Base85 module.
+/// Base85 module, with the RFC1924 alphabet applied to groups of 4 bytes as in git.
 pub struct Base85;
 
+/// Adobe Ascii85 module, between `<~` and `~>`.
+/// A group of 4 null bytes is encoded as `z`, and the whitespaces are ignored when decoding.
+pub struct Ascii85;
+
+/// btoa module, with the Ascii85 alphabet.
+/// A group of 4 null bytes is encoded as `z` and a group of 4 spaces as `y`.
+/// When decoding, the whitespaces and the `xbtoa` header and trailer lines are ignored.
+pub struct Btoa;
+
+/// ZeroMQ Z85 module, with an alphabet suitable for source code.
+pub struct Z85;
+
+/// RFC1924 module, encoding IPv6 addresses as 128-bit numbers of 20 characters.
+/// Longer data is encoded by blocks of 16 bytes, and the last block is encoded with the minimal
+/// number of characters for its length.
+pub struct Base85Rfc1924;
+
 use super::*;
 
+const RFC1924: &[u8; 85] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+const ASCII85: &[u8; 85] =
+    b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu";
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// Number of characters encoding a block of RFC1924, for each number of bytes.
+const RFC1924_WIDTHS: [usize; 17] = [0, 2, 3, 4, 5, 7, 8, 9, 10, 12, 13, 14, 15, 17, 18, 19, 20];
+
+/// Marks the bytes which are not in an alphabet, in a decoding table.
+const INVALID: u8 = 0xff;
+
 impl Base for Base85 {
     fn get_metadata(&self) -> &'static BaseMetadata {
         &BaseMetadata {
             name: "base85",
             short_name: "b85",
+            base: "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        encode_groups(RFC1924, plain, |_| None)
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_groups(&decode_table(RFC1924), enc, |_| None)
+    }
+}
+
+impl Base for Ascii85 {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "ascii85",
+            short_name: "a85",
             base: "!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu",
             padding: None,
         }
     }
 
-    fn encode(&self, plain: &[u8]) -> String {
-        base85::encode(plain)
+    fn is_valid(&self, encoded: &[u8]) -> bool {
+        strip_ascii85(encoded).is_ok_and(|data| {
+            data.iter()
+                .all(|&b| ASCII85.contains(&b) || ascii85_shortcut(b).is_some())
+        })
     }
 
-    fn decode(&self, enc: &str) -> Result<Vec<u8>, DecodeError> {
-        base85::decode(enc).ok_or(DecodeError::Error)
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let mut enc = b"<~".to_vec();
+        enc.extend(encode_groups(ASCII85, plain, |group| {
+            (group == 0).then_some(b'z')
+        }));
+        enc.extend(b"~>");
+        enc
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_groups(
+            &decode_table(ASCII85),
+            &strip_ascii85(enc)?,
+            ascii85_shortcut,
+        )
     }
 }
 
+impl Base for Btoa {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "btoa",
+            short_name: "btoa",
+            base: "!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu",
+            padding: None,
+        }
+    }
+
+    fn is_valid(&self, encoded: &[u8]) -> bool {
+        strip_btoa(encoded)
+            .iter()
+            .all(|&b| ASCII85.contains(&b) || btoa_shortcut(b).is_some())
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        encode_groups(ASCII85, plain, |group| match group {
+            0 => Some(b'z'),
+            0x20202020 => Some(b'y'),
+            _ => None,
+        })
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_groups(&decode_table(ASCII85), &strip_btoa(enc), btoa_shortcut)
+    }
+}
+
+impl Base for Z85 {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "z85",
+            short_name: "z85",
+            base: "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        encode_groups(Z85_ALPHABET, plain, |_| None)
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_groups(&decode_table(Z85_ALPHABET), enc, |_| None)
+    }
+}
+
+impl Base for Base85Rfc1924 {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base85-rfc1924",
+            short_name: "b85rfc",
+            base: "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let mut enc = Vec::with_capacity(plain.len().div_ceil(16) * 20);
+        for block in plain.chunks(16) {
+            let mut value = block.iter().fold(0u128, |acc, &b| acc << 8 | b as u128);
+            let start = enc.len();
+            for _ in 0..RFC1924_WIDTHS[block.len()] {
+                enc.push(RFC1924[(value % 85) as usize]);
+                value /= 85;
+            }
+            enc[start..].reverse();
+        }
+        enc
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let table = decode_table(RFC1924);
+        let mut plain = Vec::with_capacity(enc.len() / 20 * 16 + 16);
+        for (i, block) in enc.chunks(20).enumerate() {
+            let len = RFC1924_WIDTHS
+                .iter()
+                .position(|&width| width == block.len())
+                .ok_or(DecodeError::InvalidLength)?;
+
+            let mut value = 0u128;
+            for (j, &c) in block.iter().enumerate() {
+                let digit = match table[c as usize] {
+                    INVALID => return Err(DecodeError::InvalidByte(i * 20 + j, c)),
+                    digit => digit as u128,
+                };
+                value = value
+                    .checked_mul(85)
+                    .and_then(|value| value.checked_add(digit))
+                    .ok_or(DecodeError::Error)?;
+            }
+            if len < 16 && value >> (len * 8) != 0 {
+                return Err(DecodeError::Error);
+            }
+            plain.extend_from_slice(&value.to_be_bytes()[16 - len..]);
+        }
+        Ok(plain)
+    }
+}
+
+/// Encode data by groups of 4 bytes giving 5 characters, a partial last group giving one more
+/// character than its number of bytes. A full group can be replaced by a shortcut character.
+fn encode_groups(
+    alphabet: &[u8; 85],
+    plain: &[u8],
+    shortcut: impl Fn(u32) -> Option<u8>,
+) -> Vec<u8> {
+    let mut enc = Vec::with_capacity(plain.len().div_ceil(4) * 5);
+    for group in plain.chunks(4) {
+        let mut bytes = [0; 4];
+        bytes[..group.len()].copy_from_slice(group);
+        let mut value = u32::from_be_bytes(bytes);
+
+        if let Some(c) = shortcut(value).filter(|_| group.len() == 4) {
+            enc.push(c);
+            continue;
+        }
+        let mut chars = [0; 5];
+        for c in chars.iter_mut().rev() {
+            *c = alphabet[(value % 85) as usize];
+            value /= 85;
+        }
+        enc.extend_from_slice(&chars[..group.len() + 1]);
+    }
+    enc
+}
+
+/// Decode data by groups of 5 characters giving 4 bytes, the missing characters of the last
+/// group being the last one of the alphabet. A shortcut character replaces a full group.
+fn decode_groups(
+    table: &[u8; 256],
+    enc: &[u8],
+    shortcut: impl Fn(u8) -> Option<u32>,
+) -> Result<Vec<u8>, DecodeError> {
+    let mut plain = Vec::with_capacity(enc.len() / 5 * 4 + 4);
+    let mut group = Vec::with_capacity(5);
+    for (i, &c) in enc.iter().enumerate() {
+        if let Some(value) = shortcut(c).filter(|_| group.is_empty()) {
+            plain.extend_from_slice(&value.to_be_bytes());
+            continue;
+        }
+        match table[c as usize] {
+            INVALID => return Err(DecodeError::InvalidByte(i, c)),
+            digit => group.push(digit as u64),
+        }
+        if group.len() == 5 {
+            plain.extend_from_slice(&group_value(&group)?.to_be_bytes());
+            group.clear();
+        }
+    }
+
+    match group.len() {
+        0 => {}
+        1 => return Err(DecodeError::InvalidLength),
+        len => {
+            group.resize(5, 84);
+            plain.extend_from_slice(&group_value(&group)?.to_be_bytes()[..len - 1]);
+        }
+    }
+    Ok(plain)
+}
+
+/// Get the value of a group of 5 digits, which must fit in 32 bits.
+fn group_value(group: &[u64]) -> Result<u32, DecodeError> {
+    let value = group.iter().fold(0, |acc, digit| acc * 85 + digit);
+    u32::try_from(value).map_err(|_| DecodeError::Error)
+}
+
+/// Get the decoding table of an alphabet: the value of each byte.
+fn decode_table(alphabet: &[u8; 85]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    for (value, &c) in alphabet.iter().enumerate() {
+        table[c as usize] = value as u8;
+    }
+    table
+}
+
+/// Get the group replaced by a shortcut character of Ascii85.
+fn ascii85_shortcut(c: u8) -> Option<u32> {
+    (c == b'z').then_some(0)
+}
+
+/// Get the group replaced by a shortcut character of btoa.
+fn btoa_shortcut(c: u8) -> Option<u32> {
+    match c {
+        b'z' => Some(0),
+        b'y' => Some(0x20202020),
+        _ => None,
+    }
+}
+
+/// Remove the `<~` and `~>` delimiters of Ascii85 and the whitespaces.
+/// The start delimiter is optional, but requires the end delimiter.
+fn strip_ascii85(enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    let enc = enc.trim_ascii();
+    let enc = match enc.strip_prefix(b"<~") {
+        Some(enc) => enc.strip_suffix(b"~>").ok_or(DecodeError::InvalidPadding)?,
+        None => enc.strip_suffix(b"~>").unwrap_or(enc),
+    };
+    Ok(enc
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect())
+}
+
+/// Remove the `xbtoa` header and trailer lines of btoa and the whitespaces.
+fn strip_btoa(enc: &[u8]) -> Vec<u8> {
+    enc.split(|&b| b == b'\n')
+        .filter(|line| !line.starts_with(b"xbtoa "))
+        .flatten()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect()
+}
+
 #[cfg(test)]
 #[cfg(not(tarpaulin_include))]
 
@@ -48,17 +330,112 @@ mod tests {
         for (plaintext, ciphertext) in TESTLIST.iter() {
             assert_eq!(
                 base.encode(plaintext),
-                *ciphertext,
+                ciphertext.as_bytes(),
                 "Encoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
             );
 
             assert_eq!(
-                base.decode(ciphertext).unwrap(),
+                base.decode(ciphertext.as_bytes()).unwrap(),
                 *plaintext,
                 "Decoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
             );
         }
     }
+
+    #[test]
+    fn test_decode_invalid() {
+        let base = Base85;
+
+        assert_eq!(base.decode(b"VP,O"), Err(DecodeError::InvalidByte(2, b',')));
+        assert_eq!(base.decode(b"VPRomV"), Err(DecodeError::InvalidLength));
+        assert_eq!(base.decode(b"~~~~~"), Err(DecodeError::Error));
+        assert_eq!(base.decode(b"~~"), Err(DecodeError::Error));
+    }
+
+    #[test]
+    fn test_ascii85() {
+        let base = Ascii85;
+        assert_eq!(base.encode(b"Hello World!"), b"<~87cURD]i,\"Ebo80~>");
+        assert_eq!(base.encode(b"\0\0\0\0a\0\0\0\0"), b"<~z@/p9-!!~>");
+        assert_eq!(base.encode(b"\0\0"), b"<~!!!~>");
+
+        assert_eq!(
+            base.decode(b"<~87cURD]i,\"Ebo80~>").unwrap(),
+            b"Hello World!"
+        );
+        assert_eq!(base.decode(b"87cURD]i,\"Ebo80").unwrap(), b"Hello World!");
+        assert_eq!(
+            base.decode(b" <~87cUR\r\nD]i,\"Ebo80~>\n").unwrap(),
+            b"Hello World!"
+        );
+        assert_eq!(base.decode(b"z@/p9-!!").unwrap(), b"\0\0\0\0a\0\0\0\0");
+        assert_eq!(base.decode(b"<~87cUR"), Err(DecodeError::InvalidPadding));
+        assert_eq!(base.decode(b"87z"), Err(DecodeError::InvalidByte(2, b'z')));
+        assert_eq!(base.decode(b"s8W-!").unwrap(), b"\xff\xff\xff\xff");
+        assert_eq!(base.decode(b"s8W-\""), Err(DecodeError::Error));
+
+        assert!(base.is_valid(b"<~87cURD]i,\"Ebo80~>"));
+        assert!(base.is_valid(b"z@/p9-!!"));
+        assert!(!base.is_valid(b"<~87cURD]i,\"Ebo80"));
+        assert!(!base.is_valid(b"87cURy"));
+    }
+
+    #[test]
+    fn test_btoa() {
+        let base = Btoa;
+        assert_eq!(base.encode(b"    \0\0\0\0Hello"), b"yz87cURDZ");
+        assert_eq!(base.decode(b"yz87cURDZ").unwrap(), b"    \0\0\0\0Hello");
+        assert_eq!(
+            base.decode(b"xbtoa Begin\nyz87c\nURDZ\nxbtoa End N 13 d E 4c S 4a R 3d2\n")
+                .unwrap(),
+            b"    \0\0\0\0Hello"
+        );
+        assert!(base.is_valid(b"xbtoa Begin\nyz87cURDZ\n"));
+        assert!(!base.is_valid(b"yz87cURDZ~"));
+    }
+
+    #[test]
+    fn test_z85() {
+        // Test vector of the ZeroMQ specification.
+        let base = Z85;
+        assert_eq!(
+            base.encode(b"\x86\x4F\xD2\x6F\xB5\x59\xF7\x5B"),
+            b"HelloWorld"
+        );
+        assert_eq!(
+            base.decode(b"HelloWorld").unwrap(),
+            b"\x86\x4F\xD2\x6F\xB5\x59\xF7\x5B"
+        );
+        assert_eq!(
+            base.decode(b"Hello~"),
+            Err(DecodeError::InvalidByte(5, b'~'))
+        );
+    }
+
+    #[test]
+    fn test_rfc1924() {
+        // Test vector of RFC1924: 1080:0:0:0:8:800:200C:417A.
+        let base = Base85Rfc1924;
+        let address = b"\x10\x80\0\0\0\0\0\0\0\x08\x08\0\x20\x0c\x41\x7a";
+        assert_eq!(base.encode(address), b"4)+k&C#VzJ4br>0wv%Yp");
+        assert_eq!(base.decode(b"4)+k&C#VzJ4br>0wv%Yp").unwrap(), address);
+
+        assert_eq!(
+            base.decode(&base.encode(b"Hello World!")).unwrap(),
+            b"Hello World!"
+        );
+        let data = [b'a'; 21];
+        assert_eq!(base.encode(&data).len(), 20 + 7);
+        assert_eq!(base.decode(&base.encode(&data)).unwrap(), data);
+
+        assert_eq!(
+            base.decode(b"~~~~~~~~~~~~~~~~~~~~"),
+            Err(DecodeError::Error)
+        );
+        assert_eq!(base.decode(b"~~"), Err(DecodeError::Error));
+        assert_eq!(base.decode(b"000000"), Err(DecodeError::InvalidLength));
+        assert_eq!(base.decode(b"0,"), Err(DecodeError::InvalidByte(1, b',')));
+    }
 }
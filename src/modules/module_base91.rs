@@ -0,0 +1,266 @@
+/// basE91 module, by Joachim Henke.
+/// Groups of 13 or 14 bits are encoded as 2 characters, depending on their value.
+pub struct Base91;
+
+/// Base92 module, by thenoviceoof.
+/// Groups of 13 bits are encoded as 2 characters, and the empty data as `~`.
+pub struct Base92;
+
+use super::*;
+
+const BASE91: &[u8; 91] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&()*+,./:;<=>?@[]^_`{|}~\"";
+const BASE92: &[u8; 91] =
+    b"!#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_abcdefghijklmnopqrstuvwxyz{|}";
+
+/// Marks the bytes which are not in an alphabet, in a decoding table.
+const INVALID: u8 = 0xff;
+
+impl Base for Base91 {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base91",
+            short_name: "b91",
+            base: "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&()*+,./:;<=>?@[]^_`{|}~\"",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let mut enc = Vec::with_capacity(plain.len() * 16 / 13 + 2);
+        let (mut bits, mut len) = (0u32, 0);
+        for &byte in plain {
+            bits |= (byte as u32) << len;
+            len += 8;
+            if len > 13 {
+                // 13 bits are enough when their value can not be confused with a 14 bits value.
+                let mut value = bits & 0x1fff;
+                if value > 88 {
+                    bits >>= 13;
+                    len -= 13;
+                } else {
+                    value = bits & 0x3fff;
+                    bits >>= 14;
+                    len -= 14;
+                }
+                enc.push(BASE91[(value % 91) as usize]);
+                enc.push(BASE91[(value / 91) as usize]);
+            }
+        }
+        if len > 0 {
+            enc.push(BASE91[(bits % 91) as usize]);
+            if len > 7 || bits > 90 {
+                enc.push(BASE91[(bits / 91) as usize]);
+            }
+        }
+        enc
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let table = decode_table(BASE91);
+        let mut plain = Vec::with_capacity(enc.len() * 14 / 16 + 1);
+        let (mut bits, mut len) = (0u32, 0);
+        let mut first = None;
+        for (i, &c) in enc.iter().enumerate() {
+            let digit = match table[c as usize] {
+                INVALID => return Err(DecodeError::InvalidByte(i, c)),
+                digit => digit as u32,
+            };
+            let Some(low) = first.take() else {
+                first = Some(digit);
+                continue;
+            };
+
+            let value = low + digit * 91;
+            bits |= value << len;
+            len += if value & 0x1fff > 88 { 13 } else { 14 };
+            while len > 7 {
+                plain.push(bits as u8);
+                bits >>= 8;
+                len -= 8;
+            }
+        }
+        if let Some(low) = first {
+            plain.push((bits | low << len) as u8);
+        }
+        Ok(plain)
+    }
+}
+
+impl Base for Base92 {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "base92",
+            short_name: "b92",
+            base: "!#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_abcdefghijklmnopqrstuvwxyz{|}~",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        if plain.is_empty() {
+            return b"~".to_vec();
+        }
+
+        let mut enc = Vec::with_capacity(plain.len() * 16 / 13 + 2);
+        let (mut bits, mut len) = (0u32, 0);
+        for &byte in plain {
+            bits = bits << 8 | byte as u32;
+            len += 8;
+            if len >= 13 {
+                len -= 13;
+                let value = bits >> len;
+                bits &= (1 << len) - 1;
+                enc.push(BASE92[(value / 91) as usize]);
+                enc.push(BASE92[(value % 91) as usize]);
+            }
+        }
+        // The remaining bits are padded with zeros to 6 bits, or to 13 bits if they do not fit.
+        match len {
+            0 => {}
+            1..=6 => enc.push(BASE92[(bits << (6 - len)) as usize]),
+            _ => {
+                let value = bits << (13 - len);
+                enc.push(BASE92[(value / 91) as usize]);
+                enc.push(BASE92[(value % 91) as usize]);
+            }
+        }
+        enc
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        if enc == b"~" {
+            return Ok(Vec::new());
+        }
+
+        let table = decode_table(BASE92);
+        let mut digits = Vec::with_capacity(enc.len());
+        for (i, &c) in enc.iter().enumerate() {
+            match table[c as usize] {
+                INVALID => return Err(DecodeError::InvalidByte(i, c)),
+                digit => digits.push(digit as u32),
+            }
+        }
+
+        let mut plain = Vec::with_capacity(enc.len() * 13 / 16 + 1);
+        let (mut bits, mut len) = (0u32, 0);
+        for pair in digits.chunks(2) {
+            let (value, value_len) = match *pair {
+                [high, low] => (high * 91 + low, 13),
+                [last] => (last, 6),
+                _ => unreachable!(),
+            };
+            if value >> value_len != 0 {
+                return Err(DecodeError::Error);
+            }
+            bits = bits << value_len | value;
+            len += value_len;
+            while len >= 8 {
+                len -= 8;
+                plain.push((bits >> len) as u8);
+                bits &= (1 << len) - 1;
+            }
+        }
+        Ok(plain)
+    }
+}
+
+/// Get the decoding table of an alphabet: the value of each byte.
+fn decode_table(alphabet: &[u8; 91]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    for (value, &c) in alphabet.iter().enumerate() {
+        table[c as usize] = value as u8;
+    }
+    table
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let base = Base91;
+
+        const TESTLIST: [(&[u8], &str); 11] = [
+            (b"Hello World!", ">OwJh>Io0Tv!8PE"),
+            (b"BaseCracker", "xD7gkvMIs!50CF"),
+            (b"\x7fELF", "qP%zR"),
+            (b"", ""),
+            (b"a", "GB"),
+            (b"aa", "@DD"),
+            (b"aaa", "@DzI"),
+            (b"aaaa", "@DGfY"),
+            (b"aaaaa", "@DGfs)A"),
+            (b"aaaaaa", "@DGfs)MC"),
+            (b"test", "fPNKd"),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                base.encode(plaintext),
+                ciphertext.as_bytes(),
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+
+        assert!(base.is_valid(b">OwJh>Io0Tv!8PE"));
+        let data = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(base.decode(&base.encode(&data)).unwrap(), data);
+        assert_eq!(
+            base.decode(b"fP NKd"),
+            Err(DecodeError::InvalidByte(2, b' '))
+        );
+    }
+
+    #[test]
+    fn test_base92() {
+        let base = Base92;
+
+        const TESTLIST: [(&[u8], &str); 11] = [
+            (b"hello world", "Fc_$aOTdKnsM*k"),
+            (b"BaseCracker", "9A2?VGd-)lnRBR"),
+            (b"\x7fELF", "Ng]Z("),
+            (b"", "~"),
+            (b"a", "D,"),
+            (b"aa", "D8*"),
+            (b"aaa", "D81Q"),
+            (b"aaaa", "D81RC"),
+            (b"aaaaa", "D81RPyB"),
+            (b"aaaaaa", "D81RPya("),
+            (b"test", "Jw_@V"),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                base.encode(plaintext),
+                ciphertext.as_bytes(),
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+
+        assert!(base.is_valid(b"Fc_$aOTdKnsM*k"));
+        assert!(base.is_valid(b"~"));
+        let data = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(base.decode(&base.encode(&data)).unwrap(), data);
+        assert_eq!(base.decode(b"Fc`$"), Err(DecodeError::InvalidByte(2, b'`')));
+        assert_eq!(base.decode(b"}}"), Err(DecodeError::Error));
+    }
+}
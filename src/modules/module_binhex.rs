@@ -0,0 +1,265 @@
+/// BinHex 4.0 module, the Macintosh file format of the `.hqx` files.
+/// The header, the data fork and the resource fork are each followed by their CRC, then are
+/// compressed with a run-length encoding and encoded with 6 bits per character between `:`.
+/// Only the data fork is decoded.
+pub struct BinHex;
+
+use super::*;
+
+const ALPHABET: &[u8; 64] = b"!\"#$%&'()*+,-012345689@ABCDEFGHIJKLMNPQRSTUVXYZ[`abcdefhijklmpqr";
+
+/// Line announcing the encoded file.
+const INTRO: &[u8] = b"(This file must be converted with BinHex 4.0)";
+/// Name of the encoded file, without type nor creator.
+const NAME: &[u8] = b"data";
+/// Maximum length of a line, including the `:` delimiters.
+const LINE_LEN: usize = 64;
+/// Marker of a run in the run-length encoding, followed by the length of the run.
+const RUN: u8 = 0x90;
+
+/// Marks the bytes which are not in an alphabet, in a decoding table.
+const INVALID: u8 = 0xff;
+
+impl Base for BinHex {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "binhex",
+            short_name: "hqx",
+            base: "!\"#$%&'()*+,-012345689@ABCDEFGHIJKLMNPQRSTUVXYZ[`abcdefhijklmpqr:\r\n",
+            padding: None,
+        }
+    }
+
+    fn is_valid(&self, encoded: &[u8]) -> bool {
+        self.decode(encoded).is_ok()
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let mut header = vec![NAME.len() as u8];
+        header.extend_from_slice(NAME);
+        // Version, type, creator and flags.
+        header.extend_from_slice(&[0; 11]);
+        header.extend_from_slice(&(plain.len() as u32).to_be_bytes());
+        header.extend_from_slice(&0u32.to_be_bytes());
+
+        let mut file = Vec::with_capacity(header.len() + plain.len() + 6);
+        for fork in [&header[..], plain, &[]] {
+            file.extend_from_slice(fork);
+            file.extend_from_slice(&crc16(fork).to_be_bytes());
+        }
+
+        let mut enc = vec![b':'];
+        for group in compress(&file).chunks(3) {
+            let value = (0..3).fold(0, |acc, i| acc << 8 | *group.get(i).unwrap_or(&0) as usize);
+            enc.extend((0..=group.len()).map(|i| ALPHABET[value >> ((3 - i) * 6) & 0x3f]));
+        }
+        enc.push(b':');
+
+        let mut lines = vec![INTRO];
+        lines.extend(enc.chunks(LINE_LEN));
+        lines.join(&b'\n')
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        self.decode_verified(enc).map(|(plain, _)| plain)
+    }
+
+    fn decode_verified(&self, enc: &[u8]) -> Result<(Vec<u8>, bool), DecodeError> {
+        let file = decompress(&decode_chars(enc)?)?;
+        let mut file = file.as_slice();
+
+        let name_len = *file.first().ok_or(DecodeError::InvalidLength)? as usize;
+        let header = take_fork(&mut file, name_len + 20)?;
+        let data_len = u32::from_be_bytes(header[name_len + 12..][..4].try_into().unwrap());
+        let rsrc_len = u32::from_be_bytes(header[name_len + 16..][..4].try_into().unwrap());
+        let data = take_fork(&mut file, data_len as usize)?;
+        take_fork(&mut file, rsrc_len as usize)?;
+        Ok((data.to_vec(), true))
+    }
+}
+
+/// Get the bytes encoded between the `:` delimiters, ignoring the line breaks.
+fn decode_chars(enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    // The data starts with a `:` at the start of a line, after the introduction line.
+    let start = (0..enc.len())
+        .find(|&i| enc[i] == b':' && (i == 0 || enc[i - 1] == b'\n'))
+        .ok_or(DecodeError::Error)?
+        + 1;
+    let len = enc[start..]
+        .iter()
+        .position(|&c| c == b':')
+        .ok_or(DecodeError::InvalidLength)?;
+
+    let table = decode_table();
+    let mut file = Vec::with_capacity(len * 3 / 4);
+    let (mut bits, mut bits_len) = (0u32, 0);
+    for (i, &c) in enc[start..start + len].iter().enumerate() {
+        if c.is_ascii_whitespace() {
+            continue;
+        }
+        let digit = match table[c as usize] {
+            INVALID => return Err(DecodeError::InvalidByte(start + i, c)),
+            digit => digit as u32,
+        };
+        bits = bits << 6 | digit;
+        bits_len += 6;
+        if bits_len >= 8 {
+            bits_len -= 8;
+            file.push((bits >> bits_len) as u8);
+            bits &= (1 << bits_len) - 1;
+        }
+    }
+    Ok(file)
+}
+
+/// Take a fork and check its CRC, from the start of the file.
+fn take_fork<'a>(file: &mut &'a [u8], len: usize) -> Result<&'a [u8], DecodeError> {
+    if file.len() < len + 2 {
+        return Err(DecodeError::InvalidLength);
+    }
+    let (fork, rest) = file.split_at(len);
+    let (crc, rest) = rest.split_at(2);
+    if crc16(fork).to_be_bytes() != crc {
+        return Err(DecodeError::InvalidChecksum);
+    }
+    *file = rest;
+    Ok(fork)
+}
+
+/// Compress the runs of at least 3 bytes, as the byte followed by the marker and the length of
+/// the run. The marker byte itself is escaped as the marker followed by 0.
+fn compress(file: &[u8]) -> Vec<u8> {
+    let mut compressed = Vec::with_capacity(file.len());
+    let literal = |compressed: &mut Vec<u8>, b| match b {
+        RUN => compressed.extend_from_slice(&[RUN, 0]),
+        b => compressed.push(b),
+    };
+    for run in file.chunk_by(|a, b| a == b) {
+        for run in run.chunks(255) {
+            literal(&mut compressed, run[0]);
+            match run.len() {
+                1 => {}
+                2 => literal(&mut compressed, run[0]),
+                len => compressed.extend_from_slice(&[RUN, len as u8]),
+            }
+        }
+    }
+    compressed
+}
+
+/// Expand the runs of the run-length encoding.
+fn decompress(compressed: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    let mut file = Vec::with_capacity(compressed.len());
+    let mut bytes = compressed.iter();
+    while let Some(&b) = bytes.next() {
+        if b != RUN {
+            file.push(b);
+            continue;
+        }
+        match bytes.next().ok_or(DecodeError::InvalidLength)? {
+            0 => file.push(RUN),
+            &len => {
+                // The run repeats the previous byte.
+                let &last = file.last().ok_or(DecodeError::Error)?;
+                file.extend(std::iter::repeat_n(last, len as usize - 1));
+            }
+        }
+    }
+    Ok(file)
+}
+
+/// Get the CRC of a fork, the CRC-16/XMODEM.
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0, |crc, &b| {
+        (0..8).fold(crc ^ (b as u16) << 8, |crc, _| match crc & 0x8000 {
+            0 => crc << 1,
+            _ => crc << 1 ^ 0x1021,
+        })
+    })
+}
+
+/// Get the decoding table of the alphabet: the value of each byte.
+fn decode_table() -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    for (value, &c) in ALPHABET.iter().enumerate() {
+        table[c as usize] = value as u8;
+    }
+    table
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let base = BinHex;
+
+        const TESTLIST: [(&[u8], &str); 4] = [
+            (b"", ":\"'4KG'%!N\"1+f3#3\"!:"),
+            (b"Cat", ":\"'4KG'%!N!i$!*!%C!Y$BA4#p3!!:"),
+            (
+                b"hello world",
+                ":\"'4KG'%!N!i,!*!%CLCSC@aXEb\"hEh*XC$[N!!!:",
+            ),
+            (
+                b"aaaaaa\x90\x90\x90\x90b",
+                ":\"'4KG'%!N!i,!*!%CLCKN!D3!*!%BKBj!!!:",
+            ),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                String::from_utf8(base.encode(plaintext)).unwrap(),
+                format!("{}\n{}", String::from_utf8_lossy(INTRO), ciphertext),
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+
+        let data = (0..=255).cycle().take(1000).collect::<Vec<u8>>();
+        let enc = base.encode(&data);
+        assert!(enc
+            .split(|&c| c == b'\n')
+            .all(|line| line.len() <= LINE_LEN));
+        assert_eq!(base.decode_verified(&enc).unwrap(), (data, true));
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let base = BinHex;
+
+        // Mail headers and line breaks from another encoder.
+        assert_eq!(
+            base.decode(b"Subject: cat\r\n\r\n:\"'4KG'%!N!i$!*!%\r\nC!Y$BA4#p3!!:\r\n")
+                .unwrap(),
+            b"Cat"
+        );
+        assert_eq!(
+            base.decode(b":\"'4KG'%!N!i$!*!%C!Y$BA4#p4!!:"),
+            Err(DecodeError::InvalidChecksum)
+        );
+        assert_eq!(
+            base.decode(b":\"'4KG'%!N!i$!*!%C!Y$BA4#p3!:"),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            base.decode(b":\"'4KG'%!N!i$!*!%C!Y$BA4#p3!!"),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            base.decode(b":\"'4KG'%!N!i$!*!%C!Y$BA4#p3o!:"),
+            Err(DecodeError::InvalidByte(27, b'o'))
+        );
+        assert_eq!(base.decode(b"Cat"), Err(DecodeError::Error));
+        assert!(!base.is_valid(b"Cat"));
+    }
+}
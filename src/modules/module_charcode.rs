@@ -0,0 +1,324 @@
+/// Character codes module, the bytes as a list of numbers, e.g. `72 101 108 108 111`.
+/// The numbers have a variable width, and may have the prefix of their radix, e.g. `0x48` or
+/// `\110`. The delimiter is detected when decoding: a comma, semicolon or colon, inside which the
+/// whitespaces group the digits, or else the whitespaces or the prefixes.
+/// A single number is only decoded with a prefix, as any short number would be a list otherwise.
+pub struct Charcode {
+    radix: u32,
+    separator: &'static str,
+    prefix: bool,
+}
+
+use super::*;
+
+/// Separators used to encode, by option name.
+const SEPARATORS: [(&str, &str); 7] = [
+    ("space", " "),
+    ("comma", ","),
+    ("semicolon", ";"),
+    ("colon", ":"),
+    ("newline", "\n"),
+    ("crlf", "\r\n"),
+    ("none", ""),
+];
+/// Delimiters detected when decoding, the whitespaces being the default one.
+const DELIMITERS: &[u8] = b",;:";
+
+impl Charcode {
+    /// Create a list of character codes in the given radix (2, 8, 10 or 16), separated by spaces.
+    pub const fn new(radix: u32) -> Self {
+        Self {
+            radix,
+            separator: " ",
+            prefix: false,
+        }
+    }
+
+    /// Get the prefixes of the radix, the first one being used to encode.
+    fn prefixes(&self) -> &'static [&'static str] {
+        match self.radix {
+            2 => &["0b", "0B"],
+            8 => &["\\", "0o", "0O"],
+            16 => &["0x", "0X", "\\x"],
+            _ => &[],
+        }
+    }
+
+    /// Get the length of the prefix at the start of the data, 0 if there is none.
+    fn prefix_len(&self, enc: &[u8]) -> usize {
+        self.prefixes()
+            .iter()
+            .find(|prefix| enc.starts_with(prefix.as_bytes()))
+            .map_or(0, |prefix| prefix.len())
+    }
+}
+
+impl Base for Charcode {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        match self.radix {
+            2 => &BaseMetadata {
+                name: "charcode-bin",
+                short_name: "cbin",
+                base: "01 ,;:bB\t\r\n",
+                padding: None,
+            },
+            8 => &BaseMetadata {
+                name: "charcode-oct",
+                short_name: "coct",
+                base: "01234567 ,;:\\oO\t\r\n",
+                padding: None,
+            },
+            16 => &BaseMetadata {
+                name: "charcode-hex",
+                short_name: "chex",
+                base: "0123456789abcdefABCDEF ,;:\\xX\t\r\n",
+                padding: None,
+            },
+            _ => &BaseMetadata {
+                name: "charcode-dec",
+                short_name: "cdec",
+                base: "0123456789 ,;:\t\r\n",
+                padding: None,
+            },
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let prefix = if self.prefix { self.prefixes()[0] } else { "" };
+        plain
+            .iter()
+            .map(|&b| match self.radix {
+                2 => format!("{}{:08b}", prefix, b),
+                8 => format!("{}{:o}", prefix, b),
+                16 => format!("{}{:02x}", prefix, b),
+                _ => b.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(self.separator)
+            .into_bytes()
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let delimiter = enc.iter().find(|c| DELIMITERS.contains(c));
+        let mut plain = Vec::with_capacity(enc.len() / 2);
+        // Whether a number has a prefix, a single number is decoded only in this case.
+        let mut prefixed = false;
+        // The offset, value and number of digits of the current number, if any.
+        let mut number: Option<(usize, u32, usize)> = None;
+        // A number must have digits, even after a prefix.
+        let finish = |number: Option<(usize, u32, usize)>| match number {
+            Some((_, value, digits)) if digits > 0 => Ok(Some(value as u8)),
+            Some((start, _, _)) => Err(DecodeError::InvalidByte(start, enc[start])),
+            None => Ok(None),
+        };
+
+        let mut i = 0;
+        while i < enc.len() {
+            let c = enc[i];
+            let prefix_len = self.prefix_len(&enc[i..]);
+            if prefix_len > 0 {
+                // A prefix starts a number, ending the previous one.
+                plain.extend(finish(number.take())?);
+                number = Some((i, 0, 0));
+                prefixed = true;
+                i += prefix_len;
+                continue;
+            }
+
+            if let Some(digit) = (c as char).to_digit(self.radix) {
+                let (_, value, digits) = number.get_or_insert((i, 0, 0));
+                *value = *value * self.radix + digit;
+                if *value > 0xff {
+                    return Err(DecodeError::Error);
+                }
+                *digits += 1;
+            } else if Some(&c) == delimiter {
+                let b = finish(number.take())?.ok_or(DecodeError::InvalidByte(i, c))?;
+                plain.push(b);
+            } else if c.is_ascii_whitespace() {
+                // The whitespaces are a delimiter, or group the digits of a number.
+                if delimiter.is_none() {
+                    plain.extend(finish(number.take())?);
+                }
+            } else {
+                return Err(DecodeError::InvalidByte(i, c));
+            }
+            i += 1;
+        }
+
+        match finish(number)? {
+            Some(b) => plain.push(b),
+            // The last number is missing.
+            None if delimiter.is_some() => return Err(DecodeError::InvalidLength),
+            None => {}
+        }
+        if plain.is_empty() || (plain.len() == 1 && !prefixed) {
+            return Err(DecodeError::InvalidLength);
+        }
+        Ok(plain)
+    }
+
+    fn with_options(&self, options: &[String]) -> Result<Box<dyn Base>, BaseError> {
+        let name = self.get_metadata().name;
+        let mut base = Charcode::new(self.radix);
+        for option in options {
+            match SEPARATORS.iter().find(|(separator, _)| separator == option) {
+                Some((_, separator)) => base.separator = separator,
+                None if option == "prefix" && !self.prefixes().is_empty() => base.prefix = true,
+                None => return Err(BaseError::UnsupportedOption(name, option.clone())),
+            }
+        }
+        // Without separator nor prefix, the numbers can not be told apart.
+        if base.separator.is_empty() && !base.prefix {
+            return Err(BaseError::UnsupportedOption(name, "none".to_string()));
+        }
+        Ok(Box::new(base))
+    }
+
+    fn options(&self) -> Vec<String> {
+        let mut options = Vec::new();
+        if let Some((name, _)) = SEPARATORS[1..]
+            .iter()
+            .find(|(_, separator)| *separator == self.separator)
+        {
+            options.push(name.to_string());
+        }
+        if self.prefix {
+            options.push("prefix".to_string());
+        }
+        options
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        const TESTLIST: [(u32, &[u8], &str); 6] = [
+            (10, b"Hello", "72 101 108 108 111"),
+            (8, b"Hello", "110 145 154 154 157"),
+            (16, b"Hello", "48 65 6c 6c 6f"),
+            (2, b"Hi", "01001000 01101001"),
+            (10, b"\n\0\xff", "10 0 255"),
+            (16, b"\x01\x02", "01 02"),
+        ];
+
+        for (radix, plaintext, ciphertext) in TESTLIST.iter() {
+            let base = Charcode::new(*radix);
+            assert_eq!(
+                base.encode(plaintext),
+                ciphertext.as_bytes(),
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_delimiters() {
+        let (dec, oct, hex, bin) = (
+            Charcode::new(10),
+            Charcode::new(8),
+            Charcode::new(16),
+            Charcode::new(2),
+        );
+
+        assert_eq!(dec.decode(b"72,101, 108 ,108,111\n").unwrap(), b"Hello");
+        assert_eq!(dec.decode(b"72\r\n101\t108  108\n111").unwrap(), b"Hello");
+        assert_eq!(dec.decode(b"72;101;108;108;111").unwrap(), b"Hello");
+        assert_eq!(bin.decode(b"0110 1000,0110 1001").unwrap(), b"hi");
+        assert_eq!(bin.decode(b"1101000 0b1101001").unwrap(), b"hi");
+        assert_eq!(oct.decode(b"\\110\\145\\154\\154\\157").unwrap(), b"Hello");
+        assert_eq!(oct.decode(b"0o110 0o151").unwrap(), b"Hi");
+        assert_eq!(hex.decode(b"0x48,0x65,0x6C").unwrap(), b"Hel");
+        assert_eq!(hex.decode(b"0x480x65 \\x6c").unwrap(), b"Hel");
+        assert_eq!(hex.decode(b"48:65:a").unwrap(), b"He\n");
+
+        assert_eq!(hex.decode(b"0x48").unwrap(), b"H");
+        assert_eq!(oct.decode(b"\\110\n").unwrap(), b"H");
+
+        // Empty data or a single number without prefix is not a list.
+        for base in [&dec, &oct, &hex, &bin] {
+            assert_eq!(base.decode(b""), Err(DecodeError::InvalidLength));
+            assert_eq!(base.decode(b"  "), Err(DecodeError::InvalidLength));
+            assert_eq!(base.decode(b"1"), Err(DecodeError::InvalidLength));
+            assert_eq!(base.decode(b"10\n"), Err(DecodeError::InvalidLength));
+        }
+        assert_eq!(dec.decode(b"72 256"), Err(DecodeError::Error));
+        assert_eq!(
+            dec.decode(b"72,,101"),
+            Err(DecodeError::InvalidByte(3, b','))
+        );
+        assert_eq!(
+            dec.decode(b"72,101;108"),
+            Err(DecodeError::InvalidByte(6, b';'))
+        );
+        assert_eq!(dec.decode(b"72,101,"), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            dec.decode(b"72 0x65"),
+            Err(DecodeError::InvalidByte(4, b'x'))
+        );
+        assert_eq!(oct.decode(b"110 8"), Err(DecodeError::InvalidByte(4, b'8')));
+        assert_eq!(bin.decode(b"hello"), Err(DecodeError::InvalidByte(0, b'h')));
+        assert_eq!(
+            hex.decode(b"0x48 0x"),
+            Err(DecodeError::InvalidByte(5, b'0'))
+        );
+        assert_eq!(
+            hex.decode(b"\\x\\x48"),
+            Err(DecodeError::InvalidByte(0, b'\\'))
+        );
+    }
+
+    #[test]
+    fn test_with_options() {
+        let options = |base: &Charcode, options: &[&str]| {
+            base.with_options(&options.iter().map(|o| o.to_string()).collect::<Vec<_>>())
+        };
+
+        let base = options(&Charcode::new(16), &["prefix", "comma"]).unwrap();
+        assert_eq!(base.encode(b"Hi"), b"0x48,0x69");
+        assert_eq!(base.get_metadata().name, "charcode-hex");
+        assert_eq!(base.options(), ["comma", "prefix"]);
+        assert!(Charcode::new(16).options().is_empty());
+        let base = options(&Charcode::new(8), &["prefix", "none"]).unwrap();
+        assert_eq!(base.encode(b"Hi\n"), b"\\110\\151\\12");
+        assert_eq!(base.decode(b"\\110\\151\\12").unwrap(), b"Hi\n");
+        let base = options(&Charcode::new(2), &["newline"]).unwrap();
+        assert_eq!(base.encode(b"Hi"), b"01001000\n01101001");
+        let base = options(&Charcode::new(10), &["crlf"]).unwrap();
+        assert_eq!(base.encode(b"Hi"), b"72\r\n105");
+
+        assert_eq!(
+            options(&Charcode::new(10), &["prefix"]).err(),
+            Some(BaseError::UnsupportedOption(
+                "charcode-dec",
+                "prefix".to_string()
+            ))
+        );
+        assert_eq!(
+            options(&Charcode::new(16), &["none"]).err(),
+            Some(BaseError::UnsupportedOption(
+                "charcode-hex",
+                "none".to_string()
+            ))
+        );
+        assert_eq!(
+            options(&Charcode::new(16), &["tab"]).err(),
+            Some(BaseError::UnsupportedOption(
+                "charcode-hex",
+                "tab".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,237 @@
+/// Unicode escapes module, as in JavaScript, JSON or Java strings.
+/// Each character is encoded as `\u` followed by its UTF-16 code units, e.g. `\u0041`, and the bytes
+/// which are not UTF-8 as lone surrogates, e.g. `\udcff`.
+/// The `\u{1F600}` code points, `\x41` characters and the usual escapes such as `\n` are decoded too.
+pub struct UnicodeEscape;
+
+/// Hex escapes module, as in C or Python byte strings.
+/// Each byte is encoded as `\x` followed by its hexadecimal value, e.g. `\x41`.
+/// Unlike in JavaScript, a `\xff` escape is decoded as a byte and not as a character.
+pub struct HexEscape;
+
+use super::*;
+
+impl Base for UnicodeEscape {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "unicode-escape",
+            short_name: "uesc",
+            base: "\\u0123456789abcdefABCDEF",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        code_points(plain)
+            .into_iter()
+            .flat_map(utf16_units)
+            .map(|unit| format!("\\u{:04x}", unit))
+            .collect::<String>()
+            .into_bytes()
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        unescape(enc, false)
+    }
+}
+
+impl Base for HexEscape {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "hex-escape",
+            short_name: "xesc",
+            base: "\\x0123456789abcdefABCDEF",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        plain
+            .iter()
+            .flat_map(|b| format!("\\x{:02x}", b).into_bytes())
+            .collect()
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        unescape(enc, true)
+    }
+}
+
+/// Decode the escapes of a string, the other characters are kept as is.
+/// The `\x` escapes are bytes, or characters as in JavaScript.
+fn unescape(enc: &[u8], hex_bytes: bool) -> Result<Vec<u8>, DecodeError> {
+    // Without any escape, the data is not encoded.
+    if !enc.contains(&b'\\') {
+        return Err(DecodeError::Error);
+    }
+    let mut plain = Vec::with_capacity(enc.len());
+    let push_char = |plain: &mut Vec<u8>, c: char| {
+        plain.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes())
+    };
+
+    let mut i = 0;
+    while i < enc.len() {
+        if enc[i] != b'\\' {
+            plain.push(enc[i]);
+            i += 1;
+            continue;
+        }
+        let invalid = DecodeError::InvalidByte(i, b'\\');
+        let escape = *enc.get(i + 1).ok_or(invalid.clone())?;
+        i += 2;
+
+        let c = match escape {
+            b'x' => {
+                let value = enc.get(i..i + 2).and_then(parse_hex).ok_or(invalid)?;
+                i += 2;
+                if hex_bytes {
+                    plain.push(value as u8);
+                    continue;
+                }
+                char::from_u32(value).unwrap()
+            }
+            b'u' if enc.get(i) == Some(&b'{') => {
+                let len = enc[i..]
+                    .iter()
+                    .take(8)
+                    .position(|&c| c == b'}')
+                    .ok_or(invalid.clone())?;
+                let value = parse_hex(&enc[i + 1..i + len]).ok_or(invalid.clone())?;
+                i += len + 1;
+                char::from_u32(value).ok_or(invalid)?
+            }
+            b'u' => {
+                let unit = enc
+                    .get(i..i + 4)
+                    .and_then(parse_hex)
+                    .ok_or(invalid.clone())?;
+                i += 4;
+                // A high surrogate must be followed by the escape of a low surrogate.
+                let mut units = vec![unit as u16];
+                if (0xd800..0xdc00).contains(&unit) && enc.get(i..i + 2) == Some(b"\\u") {
+                    if let Some(low) = enc.get(i + 2..i + 6).and_then(parse_hex) {
+                        units.push(low as u16);
+                        i += 6;
+                    }
+                }
+                push_utf16(&mut plain, &units).ok_or(invalid)?;
+                continue;
+            }
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'0' => '\0',
+            b'b' => '\x08',
+            b'f' => '\x0c',
+            b'v' => '\x0b',
+            b'\\' | b'\'' | b'"' | b'/' => escape as char,
+            _ => return Err(invalid),
+        };
+        push_char(&mut plain, c);
+    }
+    Ok(plain)
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let base = UnicodeEscape;
+
+        const TESTLIST: [(&[u8], &str); 4] = [
+            (b"flag", "\\u0066\\u006c\\u0061\\u0067"),
+            (
+                "caf\u{e9} \u{1f600}".as_bytes(),
+                "\\u0063\\u0061\\u0066\\u00e9\\u0020\\ud83d\\ude00",
+            ),
+            (b"\n\0", "\\u000a\\u0000"),
+            (b"\xffA\xc3", "\\udcff\\u0041\\udcc3"),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                base.encode(plaintext),
+                ciphertext.as_bytes(),
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_mixed() {
+        let base = UnicodeEscape;
+
+        assert_eq!(
+            base.decode(br#"fl\x61g{\u{1F600}\t\"\\\/}\xe9"#).unwrap(),
+            "fla\u{67}{\u{1f600}\t\"\\/}\u{e9}".as_bytes()
+        );
+        assert_eq!(
+            base.decode(b"\\ud83d"),
+            Err(DecodeError::InvalidByte(0, b'\\'))
+        );
+        assert_eq!(
+            base.decode(b"a\\ude00\\ud83d"),
+            Err(DecodeError::InvalidByte(1, b'\\'))
+        );
+        assert_eq!(
+            base.decode(b"\\u{110000}"),
+            Err(DecodeError::InvalidByte(0, b'\\'))
+        );
+        assert_eq!(base.decode(b"\\q"), Err(DecodeError::InvalidByte(0, b'\\')));
+        assert_eq!(base.decode(b"flag"), Err(DecodeError::Error));
+        assert_eq!(base.decode(b"a\\"), Err(DecodeError::InvalidByte(1, b'\\')));
+        assert_eq!(
+            base.decode(b"\\u12"),
+            Err(DecodeError::InvalidByte(0, b'\\'))
+        );
+    }
+
+    #[test]
+    fn test_hex_escape() {
+        let base = HexEscape;
+
+        const TESTLIST: [(&[u8], &str); 3] = [
+            (b"flag", "\\x66\\x6c\\x61\\x67"),
+            (b"\x7fELF\xff", "\\x7f\\x45\\x4c\\x46\\xff"),
+            (b"\n", "\\x0a"),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                base.encode(plaintext),
+                ciphertext.as_bytes(),
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+
+        assert_eq!(
+            base.decode(b"\\x41\\n\\u00e9").unwrap(),
+            "A\n\u{e9}".as_bytes()
+        );
+        assert_eq!(base.encode(b""), b"");
+        assert_eq!(base.decode(b""), Err(DecodeError::Error));
+        assert_eq!(
+            base.decode(b"\\x4"),
+            Err(DecodeError::InvalidByte(0, b'\\'))
+        );
+    }
+}
@@ -13,11 +13,11 @@ impl Base for Hex {
         }
     }
 
-    fn encode(&self, plain: &[u8]) -> String {
-        hex::encode(plain)
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        hex::encode(plain).into_bytes()
     }
 
-    fn decode(&self, enc: &str) -> Result<Vec<u8>, DecodeError> {
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
         hex::decode(enc).map_err(|e| match e {
             hex::FromHexError::InvalidHexCharacter { c, index } => {
                 DecodeError::InvalidByte(index, c as u8)
@@ -54,13 +54,13 @@ mod tests {
         for (plaintext, ciphertext) in TESTLIST.iter() {
             assert_eq!(
                 base.encode(plaintext),
-                *ciphertext,
+                ciphertext.as_bytes(),
                 "Encoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
             );
 
             assert_eq!(
-                base.decode(ciphertext).unwrap(),
+                base.decode(ciphertext.as_bytes()).unwrap(),
                 *plaintext,
                 "Decoding \"{}\" failed",
                 unsafe { std::str::from_utf8_unchecked(plaintext) }
@@ -0,0 +1,179 @@
+/// HTML entities module.
+/// The HTML special characters are encoded as named entities, and the other non printable or non
+/// ASCII characters as hexadecimal references, e.g. `&#xE9;`. The bytes which are not UTF-8 are
+/// referenced as lone surrogates, e.g. `&#xDCFF;`. The decimal references and the most common named
+/// entities are decoded too.
+pub struct Html;
+
+use super::*;
+
+/// Named entities and their character, the first ones being used to encode.
+const ENTITIES: [(&str, char); 24] = [
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{a0}'),
+    ("copy", '©'),
+    ("reg", '®'),
+    ("trade", '™'),
+    ("deg", '°'),
+    ("plusmn", '±'),
+    ("times", '×'),
+    ("divide", '÷'),
+    ("middot", '·'),
+    ("sect", '§'),
+    ("para", '¶'),
+    ("euro", '€'),
+    ("pound", '£'),
+    ("laquo", '«'),
+    ("raquo", '»'),
+    ("hellip", '…'),
+    ("ndash", '–'),
+    ("mdash", '—'),
+    ("bull", '•'),
+];
+/// Number of entities used to encode, `&apos;` is not one as it is not in HTML 4.
+const ENCODED_ENTITIES: usize = 4;
+/// Maximum length of an entity between `&` and `;`.
+const MAX_ENTITY_LEN: usize = 10;
+
+impl Base for Html {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "html",
+            short_name: "html",
+            base: " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~\t\r\n",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let mut enc = String::with_capacity(plain.len());
+        for code_point in code_points(plain) {
+            let c = char::from_u32(code_point);
+            match ENTITIES[..ENCODED_ENTITIES]
+                .iter()
+                .find(|(_, e)| Some(*e) == c)
+            {
+                Some((name, _)) => enc.push_str(&format!("&{};", name)),
+                None => match c {
+                    Some(c) if c == ' ' || c.is_ascii_graphic() || c == '\n' => enc.push(c),
+                    _ => enc.push_str(&format!("&#x{:X};", code_point)),
+                },
+            }
+        }
+        enc.into_bytes()
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        // Without any entity, the data is not encoded.
+        if !enc.contains(&b'&') {
+            return Err(DecodeError::Error);
+        }
+        let mut plain = Vec::with_capacity(enc.len());
+        let mut i = 0;
+        while i < enc.len() {
+            if enc[i] != b'&' {
+                plain.push(enc[i]);
+                i += 1;
+                continue;
+            }
+            let len = enc[i + 1..]
+                .iter()
+                .take(MAX_ENTITY_LEN + 1)
+                .position(|&c| c == b';')
+                .ok_or(DecodeError::InvalidByte(i, b'&'))?;
+            decode_entity(&enc[i + 1..i + 1 + len])
+                .and_then(|code_point| push_code_point(&mut plain, code_point))
+                .ok_or(DecodeError::InvalidByte(i, b'&'))?;
+            i += len + 2;
+        }
+        Ok(plain)
+    }
+}
+
+/// Get the code point of an entity, without its `&` and `;`.
+fn decode_entity(entity: &[u8]) -> Option<u32> {
+    Some(match entity {
+        [b'#', b'x' | b'X', hex @ ..] => parse_hex(hex)?,
+        [b'#', dec @ ..] if !dec.is_empty() => dec.iter().try_fold(0u32, |value, &digit| {
+            value
+                .checked_mul(10)?
+                .checked_add((digit as char).to_digit(10)?)
+        })?,
+        name => {
+            return ENTITIES
+                .iter()
+                .find(|(entity, _)| entity.as_bytes() == name)
+                .map(|&(_, c)| c as u32)
+        }
+    })
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let base = Html;
+
+        const TESTLIST: [(&[u8], &str); 6] = [
+            (b"<b>Tom & Jerry</b>", "&lt;b&gt;Tom &amp; Jerry&lt;/b&gt;"),
+            (b"say \"hi\" it's", "say &quot;hi&quot; it's"),
+            ("caf\u{e9} \u{1f600}".as_bytes(), "caf&#xE9; &#x1F600;"),
+            (b"tab\tnul\0", "tab&#x9;nul&#x0;"),
+            (b"\xff\x00A", "&#xDCFF;&#x0;A"),
+            (b"a&b", "a&amp;b"),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                base.encode(plaintext),
+                ciphertext.as_bytes(),
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_references() {
+        let base = Html;
+
+        assert_eq!(base.encode(b"flag{html}"), b"flag{html}");
+        assert_eq!(base.decode(b"flag{html}"), Err(DecodeError::Error));
+
+        assert_eq!(
+            base.decode(b"&#x66;&#108;&#X61;&#103;&#123;&copy;&apos;&#x7D;")
+                .unwrap(),
+            "flag{\u{a9}'}".as_bytes()
+        );
+        assert_eq!(
+            base.decode(b"a & b"),
+            Err(DecodeError::InvalidByte(2, b'&'))
+        );
+        assert_eq!(
+            base.decode(b"&unknown;"),
+            Err(DecodeError::InvalidByte(0, b'&'))
+        );
+        assert_eq!(
+            base.decode(b"&#xD800;"),
+            Err(DecodeError::InvalidByte(0, b'&'))
+        );
+        assert_eq!(
+            base.decode(b"&#-65;"),
+            Err(DecodeError::InvalidByte(0, b'&'))
+        );
+    }
+}
@@ -0,0 +1,148 @@
+/// Quoted-printable module (RFC 2045), used in mails.
+/// The non printable bytes and `=` are encoded as `=` followed by their hexadecimal value, and the
+/// lines longer than 76 characters are split by soft line breaks, `=` at the end of a line.
+pub struct QuotedPrintable;
+
+use super::*;
+
+/// Maximum length of a line, including the `=` of a soft line break.
+const LINE_LEN: usize = 76;
+
+impl Base for QuotedPrintable {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "quoted-printable",
+            short_name: "qp",
+            base: " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~\t\r\n",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let mut enc = Vec::with_capacity(plain.len() * 3);
+        let mut line_len = 0;
+        for (i, &b) in plain.iter().enumerate() {
+            // The line breaks are kept, as CRLF or LF.
+            if matches!(&plain[i..], [b'\n', ..] | [b'\r', b'\n', ..]) {
+                enc.push(b);
+                line_len = 0;
+                continue;
+            }
+
+            // The spaces at the end of a line would be stripped by the mail transports.
+            let at_line_end = matches!(&plain[i + 1..], [] | [b'\n', ..] | [b'\r', b'\n', ..]);
+            let literal = match b {
+                b'=' => false,
+                b' ' | b'\t' => !at_line_end,
+                b => b.is_ascii_graphic(),
+            };
+            let token = if literal {
+                vec![b]
+            } else {
+                format!("={:02X}", b).into_bytes()
+            };
+
+            // Keep room for the `=` of a soft line break, unless it is the end of a line.
+            let max_len = if at_line_end { LINE_LEN } else { LINE_LEN - 1 };
+            if line_len + token.len() > max_len {
+                enc.extend_from_slice(b"=\r\n");
+                line_len = 0;
+            }
+            enc.extend_from_slice(&token);
+            line_len += token.len();
+        }
+        enc
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        // Without any encoded byte nor soft line break, the data is not encoded.
+        if !enc.contains(&b'=') {
+            return Err(DecodeError::Error);
+        }
+        let mut plain = Vec::with_capacity(enc.len());
+        let mut i = 0;
+        while i < enc.len() {
+            if enc[i] != b'=' {
+                plain.push(enc[i]);
+                i += 1;
+                continue;
+            }
+            match &enc[i + 1..] {
+                // Soft line break.
+                [b'\r', b'\n', ..] => i += 3,
+                [b'\n', ..] => i += 2,
+                [high, low, ..] => {
+                    let value =
+                        parse_hex(&[*high, *low]).ok_or(DecodeError::InvalidByte(i, b'='))?;
+                    plain.push(value as u8);
+                    i += 3;
+                }
+                _ => return Err(DecodeError::InvalidByte(i, b'=')),
+            }
+        }
+        Ok(plain)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let base = QuotedPrintable;
+
+        const TESTLIST: [(&[u8], &str); 6] = [
+            (b"a=b", "a=3Db"),
+            ("caf\u{e9}".as_bytes(), "caf=C3=A9"),
+            (b"trailing space \r\nnext line\t\nend ", "trailing space=20\r\nnext line=09\nend=20"),
+            (b"\x7fELF\0", "=7FELF=00"),
+            (b"flag{qp=}", "flag{qp=3D}"),
+            (
+                b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt.",
+                "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tem=\r\npor incididunt.",
+            ),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                base.encode(plaintext),
+                ciphertext.as_bytes(),
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+
+        let data = (0..=255).cycle().take(1000).collect::<Vec<u8>>();
+        let enc = base.encode(&data);
+        assert!(enc.split(|&c| c == b'\n').all(|line| line
+            .strip_suffix(b"\r")
+            .unwrap_or(line)
+            .len()
+            <= LINE_LEN));
+        assert_eq!(base.decode(&enc).unwrap(), data);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let base = QuotedPrintable;
+
+        assert_eq!(
+            base.decode(b"caf=c3=a9=\n!").unwrap(),
+            "caf\u{e9}!".as_bytes()
+        );
+        assert_eq!(base.encode(b"flag{qp}"), b"flag{qp}");
+        assert_eq!(base.decode(b"flag{qp}"), Err(DecodeError::Error));
+        assert_eq!(base.decode(b"a=3"), Err(DecodeError::InvalidByte(1, b'=')));
+        assert_eq!(base.decode(b"a=G0"), Err(DecodeError::InvalidByte(1, b'=')));
+        assert_eq!(base.decode(b"a="), Err(DecodeError::InvalidByte(1, b'=')));
+    }
+}
@@ -0,0 +1,104 @@
+/// URL module, percent-encoding (RFC 3986).
+/// Every byte but the unreserved characters is encoded as `%` followed by its hexadecimal value.
+/// The `+` of the HTML forms is not decoded as a space, as it is a base64 character.
+pub struct Url;
+
+use super::*;
+
+impl Base for Url {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "url",
+            short_name: "url",
+            base: "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~%",
+            padding: None,
+        }
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let mut enc = Vec::with_capacity(plain.len() * 3);
+        for &b in plain {
+            if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+                enc.push(b);
+            } else {
+                enc.extend_from_slice(format!("%{:02X}", b).as_bytes());
+            }
+        }
+        enc
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        // Without any percent-encoded byte, the data is not encoded.
+        if !enc.contains(&b'%') {
+            return Err(DecodeError::Error);
+        }
+        let mut plain = Vec::with_capacity(enc.len());
+        let mut i = 0;
+        while i < enc.len() {
+            if enc[i] != b'%' {
+                plain.push(enc[i]);
+                i += 1;
+                continue;
+            }
+            let value = enc
+                .get(i + 1..i + 3)
+                .and_then(parse_hex)
+                .ok_or(DecodeError::InvalidByte(i, b'%'))?;
+            plain.push(value as u8);
+            i += 3;
+        }
+        Ok(plain)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let base = Url;
+
+        const TESTLIST: [(&[u8], &str); 6] = [
+            (b"Hello World!", "Hello%20World%21"),
+            (b"a=1&b=2+3", "a%3D1%26b%3D2%2B3"),
+            (b"flag{url-safe_~.}", "flag%7Burl-safe_~.%7D"),
+            (b"\x7fELF\0", "%7FELF%00"),
+            ("caf\u{e9}".as_bytes(), "caf%C3%A9"),
+            (b"\xff\x00", "%FF%00"),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                base.encode(plaintext),
+                ciphertext.as_bytes(),
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_mixed() {
+        let base = Url;
+
+        assert_eq!(
+            base.decode(b"/search?q=caf%c3%a9+au+lait").unwrap(),
+            "/search?q=caf\u{e9}+au+lait".as_bytes()
+        );
+        assert_eq!(base.encode(b"flag"), b"flag");
+        assert_eq!(base.decode(b"flag"), Err(DecodeError::Error));
+        assert_eq!(base.decode(b"100%"), Err(DecodeError::InvalidByte(3, b'%')));
+        assert_eq!(base.decode(b"%2"), Err(DecodeError::InvalidByte(0, b'%')));
+        assert_eq!(base.decode(b"a%zz"), Err(DecodeError::InvalidByte(1, b'%')));
+        assert_eq!(base.decode(b"%+1"), Err(DecodeError::InvalidByte(0, b'%')));
+    }
+}
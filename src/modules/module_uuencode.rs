@@ -0,0 +1,335 @@
+/// Uuencode module, between the `begin` and `end` lines of a file.
+/// Each line starts with its number of bytes, then encodes up to 45 bytes as groups of 4
+/// characters, and an empty line ends the data.
+pub struct Uuencode;
+
+/// Uuencode module, without the `begin` and `end` lines.
+pub struct UuencodeRaw;
+
+/// Xxencode module, between the `begin` and `end` lines of a file.
+/// It is uuencode with an alphabet surviving the conversions between ASCII and EBCDIC.
+pub struct Xxencode;
+
+/// Xxencode module, without the `begin` and `end` lines.
+pub struct XxencodeRaw;
+
+use super::*;
+
+/// Uuencode alphabet, the space being replaced with `` ` `` for 0 as most encoders do.
+const UU_ALPHABET: &[u8; 64] =
+    b"`!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_";
+const XX_ALPHABET: &[u8; 64] = b"+-0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Maximum number of bytes encoded in a line.
+const LINE_LEN: usize = 45;
+/// Header of an encoded file, with its permissions and name.
+const BEGIN: &[u8] = b"begin 644 data";
+const END: &[u8] = b"end";
+
+/// Marks the bytes which are not in an alphabet, in a decoding table.
+const INVALID: u8 = 0xff;
+
+impl Base for Uuencode {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "uuencode",
+            short_name: "uu",
+            base: " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~\r\n",
+            padding: None,
+        }
+    }
+
+    fn is_valid(&self, encoded: &[u8]) -> bool {
+        self.decode(encoded).is_ok()
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        frame(encode_lines(UU_ALPHABET, plain))
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_lines(&uu_table(), unframe(enc)?)
+    }
+}
+
+impl Base for UuencodeRaw {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "uuencode-raw",
+            short_name: "uur",
+            base: " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`\r\n",
+            padding: None,
+        }
+    }
+
+    fn is_valid(&self, encoded: &[u8]) -> bool {
+        self.decode(encoded).is_ok()
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        encode_lines(UU_ALPHABET, plain)
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_lines(&uu_table(), lines(enc))
+    }
+}
+
+impl Base for Xxencode {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "xxencode",
+            short_name: "xx",
+            base: " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~\r\n",
+            padding: None,
+        }
+    }
+
+    fn is_valid(&self, encoded: &[u8]) -> bool {
+        self.decode(encoded).is_ok()
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        frame(encode_lines(XX_ALPHABET, plain))
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_lines(&decode_table(XX_ALPHABET), unframe(enc)?)
+    }
+}
+
+impl Base for XxencodeRaw {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "xxencode-raw",
+            short_name: "xxr",
+            base: "+-0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz\r\n",
+            padding: None,
+        }
+    }
+
+    fn is_valid(&self, encoded: &[u8]) -> bool {
+        self.decode(encoded).is_ok()
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        encode_lines(XX_ALPHABET, plain)
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        decode_lines(&decode_table(XX_ALPHABET), lines(enc))
+    }
+}
+
+/// Encode the data as lines, each starting with its number of bytes, ended by an empty line.
+fn encode_lines(alphabet: &[u8; 64], plain: &[u8]) -> Vec<u8> {
+    let mut enc = Vec::with_capacity(plain.len() * 4 / 3 + plain.len() / LINE_LEN * 2 + 2);
+    for line in plain.chunks(LINE_LEN) {
+        enc.push(alphabet[line.len()]);
+        for group in line.chunks(3) {
+            let value = (0..3).fold(0, |acc, i| acc << 8 | *group.get(i).unwrap_or(&0) as usize);
+            enc.extend((0..4).rev().map(|i| alphabet[value >> (i * 6) & 0x3f]));
+        }
+        enc.push(b'\n');
+    }
+    enc.push(alphabet[0]);
+    enc
+}
+
+/// Decode the lines of the data, until the empty line.
+/// Each line must have exactly the number of characters needed by its number of bytes.
+fn decode_lines<'a>(
+    table: &[u8; 256],
+    lines: impl Iterator<Item = (usize, &'a [u8])>,
+) -> Result<Vec<u8>, DecodeError> {
+    let mut plain = Vec::new();
+    for (offset, line) in lines {
+        let digits = line
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| match table[c as usize] {
+                INVALID => Err(DecodeError::InvalidByte(offset + i, c)),
+                digit => Ok(digit as usize),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let (&len, digits) = digits.split_first().ok_or(DecodeError::Error)?;
+        if len == 0 {
+            return Ok(plain);
+        }
+        if len > LINE_LEN || digits.len() != len.div_ceil(3) * 4 {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        let line_plain = digits.chunks(4).flat_map(|group| {
+            let value = group.iter().fold(0, |acc, digit| acc << 6 | digit);
+            [(value >> 16) as u8, (value >> 8) as u8, value as u8]
+        });
+        plain.extend(line_plain.take(len));
+    }
+    // The empty line ending the data is missing.
+    Err(DecodeError::InvalidLength)
+}
+
+/// Split the data in lines with their offset, ignoring the line breaks and the blank lines.
+fn lines(enc: &[u8]) -> impl Iterator<Item = (usize, &[u8])> + Clone {
+    enc.split(|&c| c == b'\n')
+        .scan(0, |offset, line| {
+            let line_offset = *offset;
+            *offset += line.len() + 1;
+            Some((line_offset, line.strip_suffix(b"\r").unwrap_or(line)))
+        })
+        .filter(|(_, line)| !line.is_empty())
+}
+
+/// Add the `begin` and `end` lines around the encoded lines.
+fn frame(lines: Vec<u8>) -> Vec<u8> {
+    [BEGIN, &lines, END].join(&b'\n')
+}
+
+/// Get the lines between the `begin` and `end` lines, ignoring the lines before the `begin` line
+/// such as the headers of a mail.
+fn unframe(enc: &[u8]) -> Result<impl Iterator<Item = (usize, &[u8])>, DecodeError> {
+    let mut lines = lines(enc).skip_while(|(_, line)| !is_begin(line));
+    lines.next().ok_or(DecodeError::Error)?;
+    let body = lines
+        .clone()
+        .take_while(|(_, line)| line.trim_ascii_end() != END);
+    if lines.all(|(_, line)| line.trim_ascii_end() != END) {
+        return Err(DecodeError::Error);
+    }
+    Ok(body)
+}
+
+/// Check if a line is a `begin` line, with the octal permissions and the name of the file.
+fn is_begin(line: &[u8]) -> bool {
+    let mut fields = line.splitn(3, |&c| c == b' ');
+    fields.next() == Some(b"begin")
+        && fields
+            .next()
+            .is_some_and(|mode| !mode.is_empty() && mode.iter().all(|c| (b'0'..=b'7').contains(c)))
+        && fields.next().is_some_and(|name| !name.is_empty())
+}
+
+/// Get the decoding table of an alphabet: the value of each byte.
+fn decode_table(alphabet: &[u8; 64]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    for (value, &c) in alphabet.iter().enumerate() {
+        table[c as usize] = value as u8;
+    }
+    table
+}
+
+/// Get the decoding table of uuencode, where the space and `` ` `` both are 0.
+fn uu_table() -> [u8; 256] {
+    let mut table = decode_table(UU_ALPHABET);
+    table[b' ' as usize] = 0;
+    table
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let base = Uuencode;
+
+        const TESTLIST: [(&[u8], &str); 5] = [
+            (b"Cat", "begin 644 data\n#0V%T\n`\nend"),
+            (b"hello world", "begin 644 data\n+:&5L;&\\@=V]R;&0`\n`\nend"),
+            (b"\x7fELF\0", "begin 644 data\n%?T5,1@``\n`\nend"),
+            (b"", "begin 644 data\n`\nend"),
+            (
+                b"The quick brown fox jumps over the lazy dog, twice.",
+                "begin 644 data\nM5&AE('%U:6-K(&)R;W=N(&9O>\"!J=6UP<R!O=F5R('1H92!L87IY(&1O9RP@\n&='=I8V4N\n`\nend",
+            ),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                String::from_utf8(base.encode(plaintext)).unwrap(),
+                *ciphertext,
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_framing() {
+        let base = Uuencode;
+
+        // Mail headers, spaces for 0, line breaks and permissions from another encoder.
+        let enc = b"From: alice\r\n\r\nbegin 600 cat.txt\r\n#0V%T\r\n \r\nend\r\n";
+        assert_eq!(base.decode(enc).unwrap(), b"Cat");
+        assert!(base.is_valid(enc));
+        assert_eq!(UuencodeRaw.decode(b"#0V%T\n`\n").unwrap(), b"Cat");
+
+        assert_eq!(base.decode(b"#0V%T\n`\nend"), Err(DecodeError::Error));
+        assert_eq!(
+            base.decode(b"begin 644 data\n#0V%T\n`"),
+            Err(DecodeError::Error)
+        );
+        assert_eq!(
+            base.decode(b"begin 9 data\n`\nend"),
+            Err(DecodeError::Error)
+        );
+        assert_eq!(
+            base.decode(b"begin 644 data\n#0V%\n`\nend"),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            UuencodeRaw.decode(b"#0V%T"),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            UuencodeRaw.decode(b"#0V%T\n#0v%T\n`"),
+            Err(DecodeError::InvalidByte(8, b'v'))
+        );
+        assert!(!UuencodeRaw.is_valid(b"hello"));
+    }
+
+    #[test]
+    fn test_xxencode() {
+        let base = Xxencode;
+
+        const TESTLIST: [(&[u8], &str); 3] = [
+            (b"Cat", "begin 644 data\n1Eq3o\n+\nend"),
+            (b"hello world", "begin 644 data\n9O4JgP4wURqxmP4E+\n+\nend"),
+            (b"", "begin 644 data\n+\nend"),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                String::from_utf8(base.encode(plaintext)).unwrap(),
+                *ciphertext,
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode(ciphertext.as_bytes()).unwrap(),
+                *plaintext,
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+
+        let data = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(base.decode(&base.encode(&data)).unwrap(), data);
+        assert_eq!(XxencodeRaw.encode(b"Cat"), b"1Eq3o\n+");
+        assert_eq!(XxencodeRaw.decode(b"1Eq3o\n+").unwrap(), b"Cat");
+        assert_eq!(
+            XxencodeRaw.decode(b"1Eq 3\n+"),
+            Err(DecodeError::InvalidByte(3, b' '))
+        );
+    }
+}
@@ -0,0 +1,221 @@
+/// yEnc module, used to post binaries on Usenet.
+/// Each byte is shifted by 42, and the critical characters are escaped with `=`. The data is
+/// between the `=ybegin` and `=yend` lines, the latter giving its size and CRC32.
+pub struct YEnc;
+
+use super::*;
+
+/// Maximum number of characters in a line, an escaped character may exceed it.
+const LINE_LEN: usize = 128;
+/// Name of the encoded file.
+const NAME: &str = "data";
+
+const BEGIN: &[u8] = b"=ybegin ";
+const PART: &[u8] = b"=ypart ";
+const END: &[u8] = b"=yend ";
+
+impl Base for YEnc {
+    fn get_metadata(&self) -> &'static BaseMetadata {
+        &BaseMetadata {
+            name: "yenc",
+            short_name: "yenc",
+            base: " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~\r\n",
+            padding: None,
+        }
+    }
+
+    fn is_valid(&self, encoded: &[u8]) -> bool {
+        self.decode(encoded).is_ok()
+    }
+
+    fn encode(&self, plain: &[u8]) -> Vec<u8> {
+        let mut enc = format!(
+            "=ybegin line={} size={} name={}\r\n",
+            LINE_LEN,
+            plain.len(),
+            NAME
+        )
+        .into_bytes();
+        let mut line_start = enc.len();
+        for (i, &b) in plain.iter().enumerate() {
+            let c = b.wrapping_add(42);
+            let at_start = enc.len() == line_start;
+            let at_end = enc.len() + 1 - line_start >= LINE_LEN || i + 1 == plain.len();
+            // Spaces and tabulations would be stripped at the line ends, and a dot at the line
+            // start would be doubled by NNTP.
+            let escaped = matches!(c, b'\0' | b'\n' | b'\r' | b'=')
+                || (matches!(c, b' ' | b'\t') && (at_start || at_end))
+                || (c == b'.' && at_start);
+            if escaped {
+                enc.extend_from_slice(&[b'=', c.wrapping_add(64)]);
+            } else {
+                enc.push(c);
+            }
+            if enc.len() - line_start >= LINE_LEN && i + 1 < plain.len() {
+                enc.extend_from_slice(b"\r\n");
+                line_start = enc.len();
+            }
+        }
+        if !plain.is_empty() {
+            enc.extend_from_slice(b"\r\n");
+        }
+        enc.extend_from_slice(
+            format!("=yend size={} crc32={:08x}", plain.len(), crc32(plain)).as_bytes(),
+        );
+        enc
+    }
+
+    fn decode(&self, enc: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        self.decode_verified(enc).map(|(plain, _)| plain)
+    }
+
+    fn decode_verified(&self, enc: &[u8]) -> Result<(Vec<u8>, bool), DecodeError> {
+        let mut lines = enc
+            .split(|&c| c == b'\n')
+            .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+            .skip_while(|line| !line.starts_with(BEGIN));
+        let begin = lines.next().ok_or(DecodeError::Error)?;
+        let multipart = field(begin, "part").is_some();
+
+        let mut plain = Vec::with_capacity(enc.len());
+        let end = loop {
+            let line = lines.next().ok_or(DecodeError::InvalidLength)?;
+            if line.starts_with(END) {
+                break line;
+            }
+            if multipart && line.starts_with(PART) {
+                continue;
+            }
+
+            let mut bytes = line.iter();
+            while let Some(&c) = bytes.next() {
+                let c = match c {
+                    b'=' => bytes.next().ok_or(DecodeError::Error)?.wrapping_sub(64),
+                    c => c,
+                };
+                plain.push(c.wrapping_sub(42));
+            }
+        };
+
+        // The size of a part is the size of its data, not of the whole file.
+        let size = field(end, "size").ok_or(DecodeError::Error)?;
+        if size.parse() != Ok(plain.len()) {
+            return Err(DecodeError::InvalidLength);
+        }
+        // Only the CRC of the part can be checked, unless it is the whole file.
+        let crc = field(end, "pcrc32").or(field(end, "crc32").filter(|_| !multipart));
+        match crc.map(|crc| u32::from_str_radix(crc, 16)) {
+            None => Ok((plain, false)),
+            Some(Ok(crc)) if crc == crc32(&plain) => Ok((plain, true)),
+            Some(_) => Err(DecodeError::InvalidChecksum),
+        }
+    }
+}
+
+/// Get the value of a field of a header line, e.g. `size=42`.
+/// The name of the file is not supported, it can contain spaces.
+fn field<'a>(line: &'a [u8], key: &str) -> Option<&'a str> {
+    std::str::from_utf8(line)
+        .ok()?
+        .split(' ')
+        .find_map(|field| field.strip_prefix(key)?.strip_prefix('='))
+}
+
+/// Get the CRC32 of the data, as in zip.
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, &b| {
+        (0..8).fold(crc ^ b as u32, |crc, _| match crc & 1 {
+            0 => crc >> 1,
+            _ => crc >> 1 ^ 0xedb88320,
+        })
+    })
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let base = YEnc;
+
+        const TESTLIST: [(&[u8], &[u8]); 5] = [
+            (
+                b"Cat",
+                b"=ybegin line=128 size=3 name=data\r\nm\x8b\x9e\r\n=yend size=3 crc32=a6130548",
+            ),
+            (
+                b"hello world",
+                b"=ybegin line=128 size=11 name=data\r\n\x92\x8f\x96\x96\x99J\xa1\x99\x9c\x96\x8e\r\n=yend size=11 crc32=0d4a1185",
+            ),
+            (
+                b"",
+                b"=ybegin line=128 size=0 name=data\r\n=yend size=0 crc32=00000000",
+            ),
+            // The critical characters, then a dot and a space which are only escaped at the line
+            // start and end.
+            (
+                b"\xd6\xe0\xe3\x13\x04",
+                b"=ybegin line=128 size=5 name=data\r\n=@=J=M=}.\r\n=yend size=5 crc32=dbced7ae",
+            ),
+            (
+                b"\x04\xf6",
+                b"=ybegin line=128 size=2 name=data\r\n=n=`\r\n=yend size=2 crc32=716b80d2",
+            ),
+        ];
+
+        for (plaintext, ciphertext) in TESTLIST.iter() {
+            assert_eq!(
+                base.encode(plaintext),
+                *ciphertext,
+                "Encoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+
+            assert_eq!(
+                base.decode_verified(ciphertext).unwrap(),
+                (plaintext.to_vec(), true),
+                "Decoding \"{}\" failed",
+                String::from_utf8_lossy(plaintext)
+            );
+        }
+
+        let data = (0..=255).cycle().take(1000).collect::<Vec<u8>>();
+        let enc = base.encode(&data);
+        assert!(enc
+            .split(|&c| c == b'\n')
+            .all(|line| line.len() <= LINE_LEN + 2));
+        assert_eq!(base.decode_verified(&enc).unwrap(), (data, true));
+    }
+
+    #[test]
+    fn test_decode_checksum() {
+        let base = YEnc;
+
+        // A part of a file, verified by the CRC of the part only.
+        let part = b"=ybegin part=1 line=128 size=20 name=cat.txt\n=ypart begin=1 end=3\nm\x8b\x9e\n=yend size=3 part=1 pcrc32=a6130548 crc32=12345678";
+        assert_eq!(base.decode_verified(part).unwrap(), (b"Cat".to_vec(), true));
+        let part = b"=ybegin part=1 line=128 size=20 name=cat.txt\n=ypart begin=1 end=3\nm\x8b\x9e\n=yend size=3 part=1 crc32=12345678";
+        assert_eq!(
+            base.decode_verified(part).unwrap(),
+            (b"Cat".to_vec(), false)
+        );
+
+        assert_eq!(
+            base.decode(
+                b"=ybegin line=128 size=3 name=data\nm\x8b\x9e\n=yend size=3 crc32=a6130549"
+            ),
+            Err(DecodeError::InvalidChecksum)
+        );
+        assert_eq!(
+            base.decode(b"=ybegin line=128 size=3 name=data\nm\x8b\n=yend size=3"),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            base.decode(b"=ybegin line=128 size=3 name=data\nm\x8b\x9e"),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(base.decode(b"m\x8b\x9e"), Err(DecodeError::Error));
+    }
+}
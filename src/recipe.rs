@@ -0,0 +1,288 @@
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::{get_base_from_name, Base, BaseError, BaseMetadata};
+
+/// Errors that can occur while parsing or loading a recipe.
+#[derive(Debug, Error)]
+pub enum RecipeError {
+    /// The recipe has no step.
+    #[error("No base specified")]
+    Empty,
+    /// A parenthesis is not closed or not opened.
+    #[error("Unbalanced parenthesis in recipe: {0}")]
+    UnbalancedParenthesis(String),
+    /// A step is not a base name followed by its options between parentheses.
+    #[error("Invalid recipe step: {0}")]
+    InvalidStep(String),
+    /// A base of the recipe can not be used.
+    #[error(transparent)]
+    Base(#[from] BaseError),
+    /// The recipe file can not be read.
+    #[error("Can not read the recipe file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The CyberChef recipe is not a list of operations in JSON.
+    #[error("Invalid CyberChef recipe: {0}")]
+    InvalidCyberChef(String),
+    /// The CyberChef operation, or its arguments, do not match any base.
+    #[error("Unsupported CyberChef operation: {0}")]
+    UnsupportedOperation(String),
+    /// The step has no equivalent CyberChef operation.
+    #[error("No CyberChef operation for {0}")]
+    NotInCyberChef(String),
+}
+
+/// A step of a recipe: the name of a base and its options, e.g. `base64(url,nopad)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecipeStep {
+    /// Name or short name of the base.
+    pub name: String,
+    /// Options of the base.
+    pub args: Vec<String>,
+}
+
+impl RecipeStep {
+    /// Create a step without options.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Get the base of the step, configured with its options.
+    pub fn base(&self) -> Result<Box<dyn Base>, BaseError> {
+        get_base_from_name(&self.name)?.with_options(&self.args)
+    }
+}
+
+impl fmt::Display for RecipeStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.args.is_empty() {
+            write!(f, "({})", self.args.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RecipeStep {
+    type Err = RecipeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RecipeError::InvalidStep(s.to_string());
+        let (name, args) = match s.split_once('(') {
+            Some((name, args)) => {
+                let args = args.strip_suffix(')').ok_or_else(invalid)?;
+                if args.contains(['(', ')']) {
+                    return Err(invalid());
+                }
+                let args = args
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|arg| !arg.is_empty())
+                    .map(String::from)
+                    .collect();
+                (name, args)
+            }
+            None => (s, Vec::new()),
+        };
+
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(invalid());
+        }
+        Ok(Self {
+            name: name.to_string(),
+            args,
+        })
+    }
+}
+
+/// A sequence of bases to apply, e.g. `base64(url,nopad)|hex`.
+///
+/// The steps are separated by `|`, or by commas and spaces outside the parentheses.
+/// In a recipe file, the lines starting with `#` are comments.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Recipe {
+    /// Steps of the recipe, in the order they are applied.
+    pub steps: Vec<RecipeStep>,
+}
+
+impl Recipe {
+    /// Create a recipe from a sequence of bases, without options.
+    pub fn from_bases<'a>(bases: impl IntoIterator<Item = &'a BaseMetadata>) -> Self {
+        Self {
+            steps: bases
+                .into_iter()
+                .map(|base| RecipeStep::new(base.name))
+                .collect(),
+        }
+    }
+
+    /// Load a recipe from a file, usually with the `.recipe` extension.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RecipeError> {
+        std::fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .parse()
+    }
+
+    /// Get the bases of the recipe, configured with their options.
+    pub fn bases(&self) -> Result<Vec<Box<dyn Base>>, BaseError> {
+        self.steps.iter().map(RecipeStep::base).collect()
+    }
+}
+
+impl fmt::Display for Recipe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                write!(f, "|")?;
+            }
+            write!(f, "{}", step)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Recipe {
+    type Err = RecipeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = Vec::new();
+        let (mut depth, mut start) = (0, 0);
+
+        for (i, c) in s.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' if depth == 0 => return Err(RecipeError::UnbalancedParenthesis(s.to_string())),
+                ')' => depth -= 1,
+                c if depth == 0 && (c == '|' || c == ',' || c.is_whitespace()) => {
+                    tokens.push(&s[start..i]);
+                    start = i + c.len_utf8();
+                }
+                _ => {}
+            }
+        }
+        if depth != 0 {
+            return Err(RecipeError::UnbalancedParenthesis(s.to_string()));
+        }
+        tokens.push(&s[start..]);
+
+        let steps = tokens
+            .into_iter()
+            .filter(|token| !token.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if steps.is_empty() {
+            return Err(RecipeError::Empty);
+        }
+        Ok(Self { steps })
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    fn step(name: &str, args: &[&str]) -> RecipeStep {
+        RecipeStep {
+            name: name.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        let recipe: Recipe = "base64(url, nopad)|hex|b32()".parse().unwrap();
+        assert_eq!(
+            recipe.steps,
+            vec![
+                step("base64", &["url", "nopad"]),
+                step("hex", &[]),
+                step("b32", &[])
+            ]
+        );
+        assert_eq!(recipe.to_string(), "base64(url,nopad)|hex|b32");
+        assert_eq!(recipe.to_string().parse::<Recipe>().unwrap(), recipe);
+    }
+
+    #[test]
+    fn test_legacy_separators() {
+        let recipe: Recipe = "b64, hex b32,,b58".parse().unwrap();
+        assert_eq!(recipe.to_string(), "b64|hex|b32|b58");
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(matches!("".parse::<Recipe>(), Err(RecipeError::Empty)));
+        assert!(matches!(" | ,".parse::<Recipe>(), Err(RecipeError::Empty)));
+        assert!(matches!(
+            "base64(url".parse::<Recipe>(),
+            Err(RecipeError::UnbalancedParenthesis(_))
+        ));
+        assert!(matches!(
+            "hex)".parse::<Recipe>(),
+            Err(RecipeError::UnbalancedParenthesis(_))
+        ));
+        assert!(matches!(
+            "base64(url)x".parse::<Recipe>(),
+            Err(RecipeError::InvalidStep(_))
+        ));
+        assert!(matches!(
+            "(url)".parse::<Recipe>(),
+            Err(RecipeError::InvalidStep(_))
+        ));
+        assert!(matches!(
+            "base64((url))".parse::<Recipe>(),
+            Err(RecipeError::InvalidStep(_))
+        ));
+    }
+
+    #[test]
+    fn test_bases() {
+        let recipe: Recipe = "b64(url)|hex".parse().unwrap();
+        let bases = recipe.bases().unwrap();
+        assert_eq!(bases[0].get_metadata().name, "base64url");
+        assert_eq!(bases[1].get_metadata().name, "hex");
+        assert_eq!(
+            Recipe::from_bases(bases.iter().map(|base| base.get_metadata())).to_string(),
+            "base64url|hex"
+        );
+
+        let recipe: Recipe = "hex(upper)".parse().unwrap();
+        assert_eq!(
+            recipe.bases().err(),
+            Some(BaseError::UnsupportedOption("hex", "upper".to_string()))
+        );
+        let recipe: Recipe = "base1337".parse().unwrap();
+        assert_eq!(
+            recipe.bases().err(),
+            Some(BaseError::NotFound("base1337".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_from_file() {
+        let path = std::env::temp_dir().join(format!(
+            "basecracker_{}_test_from_file.recipe",
+            std::process::id()
+        ));
+        std::fs::write(&path, "# JWT payload\nbase64(url,nopad)\n  # then\nhex\n").unwrap();
+        let recipe = Recipe::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(recipe.to_string(), "base64(url,nopad)|hex");
+        assert!(matches!(Recipe::from_file(&path), Err(RecipeError::Io(_))));
+    }
+}
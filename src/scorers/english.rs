@@ -0,0 +1,239 @@
+//! English language statistics used by the scorers.
+
+/// Frequency of each letter from `a` to `z` in English texts, in percent.
+pub const LETTER_FREQUENCIES: [f32; 26] = [
+    8.167, 1.492, 2.782, 4.253, 12.702, 2.228, 2.015, 6.094, 6.966, 0.153, 0.772, 4.025, 2.406,
+    6.749, 7.507, 1.929, 0.095, 5.987, 6.327, 9.056, 2.758, 0.978, 2.360, 0.150, 1.974, 0.074,
+];
+
+/// Most common English bigrams and their frequency, in percent.
+pub const BIGRAMS: &[(&str, f32)] = &[
+    ("th", 3.56),
+    ("he", 3.07),
+    ("in", 2.43),
+    ("er", 2.05),
+    ("an", 1.99),
+    ("re", 1.85),
+    ("on", 1.76),
+    ("at", 1.49),
+    ("en", 1.45),
+    ("nd", 1.35),
+    ("ti", 1.34),
+    ("es", 1.34),
+    ("or", 1.28),
+    ("te", 1.20),
+    ("of", 1.17),
+    ("ed", 1.17),
+    ("is", 1.13),
+    ("it", 1.12),
+    ("al", 1.09),
+    ("ar", 1.07),
+    ("st", 1.05),
+    ("to", 1.04),
+    ("nt", 1.04),
+    ("ng", 0.95),
+    ("se", 0.93),
+    ("ha", 0.93),
+    ("as", 0.87),
+    ("ou", 0.87),
+    ("io", 0.83),
+    ("le", 0.83),
+    ("ve", 0.83),
+    ("co", 0.79),
+    ("me", 0.79),
+    ("de", 0.76),
+    ("hi", 0.76),
+    ("ri", 0.73),
+    ("ro", 0.73),
+    ("ic", 0.70),
+    ("ne", 0.69),
+    ("ea", 0.69),
+    ("ra", 0.69),
+    ("ce", 0.65),
+    ("li", 0.62),
+    ("ch", 0.60),
+    ("ll", 0.58),
+    ("be", 0.58),
+    ("ma", 0.57),
+    ("si", 0.55),
+    ("om", 0.55),
+    ("ur", 0.54),
+];
+
+/// Most common English quadgrams and their frequency, in percent.
+pub const QUADGRAMS: &[(&str, f32)] = &[
+    ("tion", 0.312),
+    ("nthe", 0.266),
+    ("ther", 0.242),
+    ("that", 0.213),
+    ("ofth", 0.193),
+    ("fthe", 0.192),
+    ("thes", 0.183),
+    ("with", 0.181),
+    ("inth", 0.172),
+    ("atio", 0.168),
+    ("othe", 0.163),
+    ("tthe", 0.163),
+    ("dthe", 0.157),
+    ("ingt", 0.156),
+    ("ethe", 0.154),
+    ("sand", 0.152),
+    ("sthe", 0.150),
+    ("here", 0.149),
+    ("thec", 0.148),
+    ("ment", 0.144),
+    ("them", 0.138),
+    ("rthe", 0.134),
+    ("thep", 0.130),
+    ("from", 0.130),
+    ("this", 0.129),
+    ("ting", 0.129),
+    ("thei", 0.127),
+    ("ngth", 0.125),
+    ("ions", 0.123),
+    ("andt", 0.123),
+];
+
+/// Common English words, plus some words usually found in CTFs.
+pub const WORDS: &[&str] = &[
+    "the",
+    "be",
+    "to",
+    "of",
+    "and",
+    "a",
+    "in",
+    "that",
+    "have",
+    "i",
+    "it",
+    "for",
+    "not",
+    "on",
+    "with",
+    "he",
+    "as",
+    "you",
+    "do",
+    "at",
+    "this",
+    "but",
+    "his",
+    "by",
+    "from",
+    "they",
+    "we",
+    "say",
+    "her",
+    "she",
+    "or",
+    "an",
+    "will",
+    "my",
+    "one",
+    "all",
+    "would",
+    "there",
+    "their",
+    "what",
+    "so",
+    "up",
+    "out",
+    "if",
+    "about",
+    "who",
+    "get",
+    "which",
+    "go",
+    "me",
+    "when",
+    "make",
+    "can",
+    "like",
+    "time",
+    "no",
+    "just",
+    "him",
+    "know",
+    "take",
+    "people",
+    "into",
+    "year",
+    "your",
+    "good",
+    "some",
+    "could",
+    "them",
+    "see",
+    "other",
+    "than",
+    "then",
+    "now",
+    "look",
+    "only",
+    "come",
+    "its",
+    "over",
+    "think",
+    "also",
+    "back",
+    "after",
+    "use",
+    "two",
+    "how",
+    "our",
+    "work",
+    "first",
+    "well",
+    "way",
+    "even",
+    "new",
+    "want",
+    "because",
+    "any",
+    "these",
+    "give",
+    "day",
+    "most",
+    "us",
+    "is",
+    "are",
+    "was",
+    "were",
+    "has",
+    "had",
+    "been",
+    "here",
+    "where",
+    "why",
+    "hello",
+    "world",
+    "yes",
+    "ok",
+    "flag",
+    "ctf",
+    "key",
+    "secret",
+    "password",
+    "pass",
+    "admin",
+    "user",
+    "data",
+    "message",
+    "text",
+    "code",
+    "test",
+    "easy",
+    "hard",
+    "try",
+    "again",
+    "found",
+    "find",
+    "win",
+    "congrats",
+    "congratulations",
+    "welcome",
+    "tool",
+    "awesome",
+    "awsome",
+];
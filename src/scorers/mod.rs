@@ -0,0 +1,218 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+mod english;
+mod scorer_dictionary;
+mod scorer_frequency;
+mod scorer_ngram;
+mod scorer_printable;
+mod scorer_utf8;
+
+pub use scorer_dictionary::DictionaryScorer;
+pub use scorer_frequency::FrequencyScorer;
+pub use scorer_ngram::NgramScorer;
+pub use scorer_printable::PrintableScorer;
+pub use scorer_utf8::Utf8Scorer;
+
+/// A scoring module, rating how plausible some decoded data is, shareable between threads.
+pub trait Scorer: Send + Sync {
+    /// Get the name of the scorer.
+    fn name(&self) -> &'static str;
+
+    /// Score the data, from 0.0 (garbage) to 1.0 (plausible).
+    fn score(&self, data: &[u8]) -> f32;
+}
+
+/// Weighted combination of scorers.
+/// The composite score is the weighted mean of the scores of each scorer.
+pub struct CompositeScorer {
+    scorers: Vec<(Box<dyn Scorer>, f32)>,
+}
+
+impl CompositeScorer {
+    /// Create an empty composite scorer.
+    pub fn new() -> Self {
+        Self {
+            scorers: Vec::new(),
+        }
+    }
+
+    /// Add a scorer with the given weight.
+    pub fn with(mut self, scorer: Box<dyn Scorer>, weight: f32) -> Self {
+        self.scorers.push((scorer, weight));
+        self
+    }
+
+    /// Create a composite scorer rating the percentage of printable UTF-8 characters.
+    pub fn utf8() -> Self {
+        Self::new().with(Box::new(Utf8Scorer), 1.0)
+    }
+
+    /// Create a composite scorer rating English plaintexts.
+    pub fn english() -> Self {
+        Self::new()
+            .with(Box::new(Utf8Scorer), 1.0)
+            .with(Box::new(FrequencyScorer), 1.0)
+            .with(Box::new(NgramScorer::bigrams()), 1.0)
+            .with(Box::new(NgramScorer::quadgrams()), 0.5)
+            .with(Box::new(DictionaryScorer), 1.0)
+    }
+
+    /// Get the scorers and their weight.
+    pub fn scorers(&self) -> &[(Box<dyn Scorer>, f32)] {
+        &self.scorers
+    }
+
+    /// Score the data and return the composite score with the score of each scorer.
+    pub fn evaluate(&self, data: &[u8]) -> (f32, Vec<(&'static str, f32)>) {
+        let scores = self
+            .scorers
+            .iter()
+            .map(|(scorer, _)| (scorer.name(), scorer.score(data)))
+            .collect::<Vec<_>>();
+
+        let total_weight = self.scorers.iter().map(|(_, weight)| weight).sum::<f32>();
+        if total_weight <= 0.0 {
+            return (0.0, scores);
+        }
+        let score = scores
+            .iter()
+            .zip(self.scorers.iter())
+            .map(|((_, score), (_, weight))| score * weight)
+            .sum::<f32>()
+            / total_weight;
+        (score, scores)
+    }
+}
+
+impl Default for CompositeScorer {
+    /// Create an empty composite scorer, as `new`.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scorer for CompositeScorer {
+    fn name(&self) -> &'static str {
+        "composite"
+    }
+
+    fn score(&self, data: &[u8]) -> f32 {
+        self.evaluate(data).0
+    }
+}
+
+impl FromStr for CompositeScorer {
+    type Err = ScorerError;
+
+    /// Parse a comma separated list of scorers with an optional weight, e.g. `utf8:2,dictionary`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|scorer| !scorer.is_empty())
+            .try_fold(Self::new(), |composite, scorer| {
+                let (name, weight) = match scorer.split_once(':') {
+                    Some((name, weight)) => (
+                        name,
+                        weight
+                            .parse::<f32>()
+                            .ok()
+                            .filter(|weight| weight.is_finite() && *weight >= 0.0)
+                            .ok_or_else(|| ScorerError::InvalidWeight(weight.to_string()))?,
+                    ),
+                    None => (scorer, 1.0),
+                };
+                Ok(composite.with(get_scorer_from_name(name)?, weight))
+            })
+    }
+}
+
+/// Get a list of all defined scorers.
+pub fn get_scorers() -> Vec<Box<dyn Scorer>> {
+    vec![
+        Box::new(PrintableScorer),
+        Box::new(Utf8Scorer),
+        Box::new(FrequencyScorer),
+        Box::new(NgramScorer::bigrams()),
+        Box::new(NgramScorer::quadgrams()),
+        Box::new(DictionaryScorer),
+    ]
+}
+
+/// Errors that can occur while getting a scorer.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ScorerError {
+    /// The scorer was not found.
+    #[error("Scorer not found: {0}")]
+    NotFound(String),
+    /// The weight of the scorer is not a finite positive number.
+    #[error("Invalid scorer weight: {0}")]
+    InvalidWeight(String),
+}
+
+/// Get a scorer from its name.
+pub fn get_scorer_from_name(name: &str) -> Result<Box<dyn Scorer>, ScorerError> {
+    get_scorers()
+        .into_iter()
+        .find(|scorer| scorer.name() == name)
+        .ok_or_else(|| ScorerError::NotFound(name.to_string()))
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_composite_evaluate() {
+        let scorer = CompositeScorer::new()
+            .with(Box::new(PrintableScorer), 3.0)
+            .with(Box::new(Utf8Scorer), 1.0);
+
+        let (score, scores) = scorer.evaluate("héllo".as_bytes());
+        assert_eq!(scores, vec![("printable", 4.0 / 6.0), ("utf8", 1.0)]);
+        assert_eq!(score, 0.75);
+        assert_eq!(CompositeScorer::new().evaluate(b"hello"), (0.0, vec![]));
+        assert_eq!(CompositeScorer::default().evaluate(b"hello"), (0.0, vec![]));
+    }
+
+    #[test]
+    fn test_composite_from_str() {
+        let scorer = "utf8:2, dictionary".parse::<CompositeScorer>().unwrap();
+        let names = scorer
+            .scorers()
+            .iter()
+            .map(|(scorer, weight)| (scorer.name(), *weight))
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![("utf8", 2.0), ("dictionary", 1.0)]);
+
+        assert_eq!(
+            "utf8,nope".parse::<CompositeScorer>().err(),
+            Some(ScorerError::NotFound("nope".to_string()))
+        );
+        assert_eq!(
+            "utf8:-1".parse::<CompositeScorer>().err(),
+            Some(ScorerError::InvalidWeight("-1".to_string()))
+        );
+        assert_eq!(
+            "utf8:inf".parse::<CompositeScorer>().err(),
+            Some(ScorerError::InvalidWeight("inf".to_string()))
+        );
+        assert_eq!(
+            "utf8:NaN".parse::<CompositeScorer>().err(),
+            Some(ScorerError::InvalidWeight("NaN".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_english_ranking() {
+        let scorer = CompositeScorer::english();
+
+        let english = scorer.score(b"flag{this_is_the_secret_message}");
+        let base64 = scorer.score(b"ZmxhZ3t0aGlzX2lzX3RoZV9zZWNyZXRfbWVzc2FnZX0=");
+        let base32 = scorer.score(b"MZWGCZ33ORUGS427NFZV65DIMVPXGZLDOJSXIX3NMVZXGYLHMV6Q====");
+        assert!(english > base64, "{english} vs {base64}");
+        assert!(english > base32, "{english} vs {base32}");
+    }
+}
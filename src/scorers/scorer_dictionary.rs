@@ -0,0 +1,48 @@
+/// Dictionary scorer.
+/// Rates the percentage of letters belonging to common English words.
+pub struct DictionaryScorer;
+
+use super::*;
+
+impl Scorer for DictionaryScorer {
+    fn name(&self) -> &'static str {
+        "dictionary"
+    }
+
+    fn score(&self, data: &[u8]) -> f32 {
+        let mut letters = 0;
+        let mut matched = 0;
+        for word in data.split(|c| !c.is_ascii_alphabetic()) {
+            if word.is_empty() {
+                continue;
+            }
+            letters += word.len();
+            let word = word.to_ascii_lowercase();
+            if english::WORDS.iter().any(|w| w.as_bytes() == word) {
+                matched += word.len();
+            }
+        }
+        if letters == 0 {
+            return 0.0;
+        }
+        matched as f32 / letters as f32
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        let scorer = DictionaryScorer;
+
+        assert_eq!(scorer.score(b""), 0.0);
+        assert_eq!(scorer.score(b"1234"), 0.0);
+        assert_eq!(scorer.score(b"Hello World!"), 1.0);
+        assert_eq!(scorer.score(b"flag{this_is_the_key}"), 1.0);
+        assert_eq!(scorer.score(b"hello qwerty"), 5.0 / 11.0);
+        assert_eq!(scorer.score(b"SGVsbG8gV29ybGQh"), 0.0);
+    }
+}
@@ -0,0 +1,64 @@
+/// Letter frequency scorer.
+/// Rates how close the letter distribution is to English using a chi-squared test,
+/// weighted by the percentage of letters and spaces.
+pub struct FrequencyScorer;
+
+use super::*;
+
+impl Scorer for FrequencyScorer {
+    fn name(&self) -> &'static str {
+        "frequency"
+    }
+
+    fn score(&self, data: &[u8]) -> f32 {
+        let mut counts = [0usize; 26];
+        let mut letters = 0;
+        let mut spaces = 0;
+        for c in data {
+            if c.is_ascii_alphabetic() {
+                counts[(c.to_ascii_lowercase() - b'a') as usize] += 1;
+                letters += 1;
+            } else if *c == b' ' {
+                spaces += 1;
+            }
+        }
+        if letters == 0 {
+            return 0.0;
+        }
+
+        let chi_squared = counts
+            .iter()
+            .zip(english::LETTER_FREQUENCIES.iter())
+            .map(|(&count, &frequency)| {
+                let expected = frequency / 100.0 * letters as f32;
+                (count as f32 - expected).powi(2) / expected
+            })
+            .sum::<f32>();
+
+        // Normalize by the number of letters to get a length independent distance.
+        let distance = chi_squared / letters as f32;
+        let text_ratio = (letters + spaces) as f32 / data.len() as f32;
+        (-distance / 2.0).exp() * text_ratio
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        let scorer = FrequencyScorer;
+
+        assert_eq!(scorer.score(b""), 0.0);
+        assert_eq!(scorer.score(b"1234"), 0.0);
+
+        let english = scorer.score(b"In the beginning of the story there was nothing");
+        let base64 = scorer.score(b"SW4gdGhlIGJlZ2lubmluZyBvZiB0aGUgc3Rvcnk=");
+        let hex = scorer.score(b"496e2074686520626567696e6e696e67");
+        assert!(english > 0.5, "{english}");
+        assert!(english > base64 * 4.0, "{english} vs {base64}");
+        assert!(english > hex * 4.0, "{english} vs {hex}");
+    }
+}
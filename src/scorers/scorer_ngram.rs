@@ -0,0 +1,101 @@
+/// N-gram scorer.
+/// Rates the average log-likelihood of the letter n-grams against English n-gram frequencies.
+pub struct NgramScorer {
+    name: &'static str,
+    n: usize,
+    table: &'static [(&'static str, f32)],
+    /// Log-likelihood of n-grams missing from the table.
+    floor: f32,
+    /// Average log-likelihood of a typical English text.
+    english: f32,
+}
+
+use super::*;
+
+impl NgramScorer {
+    /// Create a scorer using English bigrams.
+    pub fn bigrams() -> Self {
+        Self {
+            name: "bigram",
+            n: 2,
+            table: english::BIGRAMS,
+            floor: -3.3,
+            english: -2.5,
+        }
+    }
+
+    /// Create a scorer using English quadgrams.
+    pub fn quadgrams() -> Self {
+        Self {
+            name: "quadgram",
+            n: 4,
+            table: english::QUADGRAMS,
+            floor: -4.3,
+            english: -3.8,
+        }
+    }
+
+    /// Get the log-likelihood of a n-gram.
+    fn log_likelihood(&self, ngram: &[u8]) -> f32 {
+        self.table
+            .iter()
+            .find(|(entry, _)| entry.as_bytes() == ngram)
+            .map_or(self.floor, |(_, frequency)| (frequency / 100.0).log10())
+    }
+}
+
+impl Scorer for NgramScorer {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn score(&self, data: &[u8]) -> f32 {
+        let letters = data
+            .iter()
+            .filter(|c| c.is_ascii_alphabetic())
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<Vec<u8>>();
+        if letters.len() < self.n {
+            return 0.0;
+        }
+
+        let ngrams = letters.windows(self.n);
+        let count = ngrams.len();
+        let average = ngrams.map(|ngram| self.log_likelihood(ngram)).sum::<f32>() / count as f32;
+        ((average - self.floor) / (self.english - self.floor)).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bigrams() {
+        let scorer = NgramScorer::bigrams();
+
+        assert_eq!(scorer.score(b""), 0.0);
+        assert_eq!(scorer.score(b"a"), 0.0);
+        assert_eq!(scorer.score(b"th"), 1.0);
+        assert_eq!(scorer.score(b"qz"), 0.0);
+
+        let english = scorer.score(b"In the beginning of the story there was nothing");
+        let base64 = scorer.score(b"SW4gdGhlIGJlZ2lubmluZyBvZiB0aGUgc3Rvcnk=");
+        assert!(english > 0.5, "{english}");
+        assert!(english > base64 * 4.0, "{english} vs {base64}");
+    }
+
+    #[test]
+    fn test_quadgrams() {
+        let scorer = NgramScorer::quadgrams();
+
+        assert_eq!(scorer.score(b"abc"), 0.0);
+        assert_eq!(scorer.score(b"tion"), 1.0);
+        assert_eq!(scorer.score(b"qzxj"), 0.0);
+
+        let english = scorer.score(b"In the beginning of the story there was nothing");
+        let base64 = scorer.score(b"SW4gdGhlIGJlZ2lubmluZyBvZiB0aGUgc3Rvcnk=");
+        assert!(english > base64, "{english} vs {base64}");
+    }
+}
@@ -0,0 +1,33 @@
+use crate::utils::printable_percentage;
+
+/// Printable scorer.
+/// Rates the percentage of printable ASCII characters.
+pub struct PrintableScorer;
+
+use super::*;
+
+impl Scorer for PrintableScorer {
+    fn name(&self) -> &'static str {
+        "printable"
+    }
+
+    fn score(&self, data: &[u8]) -> f32 {
+        printable_percentage(data)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        let scorer = PrintableScorer;
+
+        assert_eq!(scorer.score(b""), 0.0);
+        assert_eq!(scorer.score(b"Hello World!"), 1.0);
+        assert_eq!(scorer.score(b"He\0lo W\0rl\0!"), 0.75);
+        assert_eq!(scorer.score("héllo".as_bytes()), 4.0 / 6.0);
+    }
+}
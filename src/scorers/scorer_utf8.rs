@@ -0,0 +1,46 @@
+/// UTF-8 scorer.
+/// Rates the percentage of bytes forming valid and printable UTF-8 characters,
+/// spaces and line breaks included.
+pub struct Utf8Scorer;
+
+use super::*;
+
+impl Scorer for Utf8Scorer {
+    fn name(&self) -> &'static str {
+        "utf8"
+    }
+
+    fn score(&self, data: &[u8]) -> f32 {
+        if data.is_empty() {
+            return 0.0;
+        }
+        let printable = data
+            .utf8_chunks()
+            .flat_map(|chunk| chunk.valid().chars())
+            .filter(|c| !c.is_control() || matches!(c, '\n' | '\r'))
+            .map(char::len_utf8)
+            .sum::<usize>();
+        printable as f32 / data.len() as f32
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        let scorer = Utf8Scorer;
+
+        assert_eq!(scorer.score(b""), 0.0);
+        assert_eq!(scorer.score(b"Hello World!\n"), 1.0);
+        assert_eq!(scorer.score("héllo wörld ✓".as_bytes()), 1.0);
+        assert_eq!(scorer.score(b"He\0lo W\0rl\0!"), 0.75);
+        assert_eq!(scorer.score(b"\xffHello\xfe"), 5.0 / 7.0);
+        assert_eq!(scorer.score(b"Hello\r\nWorld"), 1.0);
+        // Other control characters are garbage, even the whitespaces.
+        assert!(scorer.score(b"\t5") < 0.9);
+        assert_eq!(scorer.score(b"\x0b\x0c"), 0.0);
+    }
+}
@@ -0,0 +1,185 @@
+//! Serde support for the crack results.
+//! The static bases and scorers are serialized by name, and resolved through their registry on load.
+//! The results of user-defined bases or scorers can be serialized, but not deserialized.
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    get_base_from_name, get_scorer_from_name, BaseMetadata, CrackData, CrackTree, Tree, Truncation,
+};
+
+/// Get the metadata of a base from its name or short name.
+fn base_metadata<E: Error>(name: &str) -> Result<&'static BaseMetadata, E> {
+    get_base_from_name(name)
+        .map(|base| base.get_metadata())
+        .map_err(E::custom)
+}
+
+impl<'de> Deserialize<'de> for BaseMetadata {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// Only the name is needed, the other fields come from the registry.
+        #[derive(Deserialize)]
+        struct Named {
+            name: String,
+        }
+
+        let named = Named::deserialize(deserializer)?;
+        base_metadata(&named.name).cloned()
+    }
+}
+
+impl<'de> Deserialize<'de> for CrackTree {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// The crack tree as serialized, before the duplicated nodes are checked.
+        #[derive(Deserialize)]
+        struct Fields {
+            tree: Tree<CrackData>,
+            truncated: Option<Truncation>,
+        }
+
+        let Fields { tree, truncated } = Fields::deserialize(deserializer)?;
+        // A duplicated node refers to another node of the tree.
+        for id in tree.pre_order(tree.root()) {
+            if let Some(first) = tree[id].duplicate_of {
+                if first == id || tree.get(first).is_none() {
+                    return Err(D::Error::custom(format!(
+                        "node {} is the duplicate of an invalid node {}",
+                        id, first
+                    )));
+                }
+            }
+        }
+        Ok(Self { tree, truncated })
+    }
+}
+
+/// (De)serialize the base of a crack data by its name.
+pub(crate) mod base_name {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        base: &Option<&'static BaseMetadata>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        base.map(|base| base.name).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<&'static BaseMetadata>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|name| base_metadata(&name))
+            .transpose()
+    }
+}
+
+/// (De)serialize the scores of a crack data, the scorers being identified by their name.
+/// Deserializing fails on the names missing from [`get_scorers`](crate::get_scorers).
+pub(crate) mod scorer_names {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        scores: &[(&'static str, f32)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        scores.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(&'static str, f32)>, D::Error> {
+        Vec::<(String, f32)>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(name, score)| {
+                get_scorer_from_name(&name)
+                    .map(|scorer| (scorer.name(), score))
+                    .map_err(D::Error::custom)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_base_metadata() {
+        let base = get_base_from_name("b64").unwrap();
+        let json = serde_json::to_string(base.get_metadata()).unwrap();
+        let metadata: BaseMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(&metadata, base.get_metadata());
+
+        assert!(serde_json::from_str::<BaseMetadata>(r#"{"name": "base1337"}"#).is_err());
+    }
+
+    #[test]
+    fn test_crack_tree() {
+        let options = CrackOptions {
+            scorer: CompositeScorer::english(),
+            min_score: 0.0,
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        let tree = crack(b"6147567362473867643239796247513d", &get_bases(), &options);
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let loaded: CrackTree = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+        assert_eq!(loaded.size(), tree.size());
+        assert_eq!(loaded.truncated(), tree.truncated());
+        for id in tree.tree().pre_order(tree.root()) {
+            assert_eq!(loaded[id], tree[id]);
+        }
+    }
+
+    #[test]
+    fn test_invalid_tree() {
+        let tree = crack(b"68656c6c6f", &get_bases(), &CrackOptions::default());
+        let mut json = serde_json::to_value(&tree).unwrap();
+
+        // Unknown child of the root node.
+        json["tree"]["nodes"][0]["children"] = serde_json::json!([5]);
+        assert!(serde_json::from_value::<CrackTree>(json.clone()).is_err());
+
+        json["tree"]["nodes"] = serde_json::json!([]);
+        assert!(serde_json::from_value::<CrackTree>(json.clone()).is_err());
+
+        let mut json = serde_json::to_value(&tree).unwrap();
+        json["tree"]["nodes"][1]["data"]["duplicate_of"] = serde_json::json!(42);
+        assert!(serde_json::from_value::<CrackTree>(json).is_err());
+    }
+
+    #[test]
+    fn test_unknown_scorer() {
+        struct Custom;
+
+        impl Scorer for Custom {
+            fn name(&self) -> &'static str {
+                "custom"
+            }
+
+            fn score(&self, _data: &[u8]) -> f32 {
+                1.0
+            }
+        }
+
+        let options = CrackOptions {
+            scorer: CompositeScorer::new().with(Box::new(Custom), 1.0),
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        let tree = crack(b"68656c6c6f", &get_bases(), &options);
+        let json = serde_json::to_string(&tree).unwrap();
+        let err = serde_json::from_str::<CrackTree>(&json).unwrap_err();
+        assert!(err.to_string().contains("custom"), "{err}");
+    }
+
+    #[test]
+    fn test_unknown_base() {
+        let data = r#"{"base": "base1337", "decoded": [], "score": 1.0, "scores": [],
+            "crib_match": false, "duplicate_of": null}"#;
+        assert!(serde_json::from_str::<CrackData>(data).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::NodeId;
+
+/// Crack search strategy, the order in which the decoded data are decoded further.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// Depth-first search, decode the first decoded data until it can not be decoded anymore.
+    #[default]
+    DepthFirst,
+    /// Breadth-first search, decode all the decoded data of a depth before the next depth.
+    BreadthFirst,
+    /// Best-first search, always decode the best scored decoded data found so far.
+    BestFirst,
+    /// Beam search, only decode the best scored decoded data of each depth.
+    Beam,
+}
+
+/// Errors that can occur while parsing a strategy.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum StrategyError {
+    /// The strategy was not found.
+    #[error("Strategy not found: {0}")]
+    NotFound(String),
+}
+
+impl FromStr for Strategy {
+    type Err = StrategyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dfs" => Ok(Strategy::DepthFirst),
+            "bfs" => Ok(Strategy::BreadthFirst),
+            "best" => Ok(Strategy::BestFirst),
+            "beam" => Ok(Strategy::Beam),
+            _ => Err(StrategyError::NotFound(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::DepthFirst => write!(f, "dfs"),
+            Strategy::BreadthFirst => write!(f, "bfs"),
+            Strategy::BestFirst => write!(f, "best"),
+            Strategy::Beam => write!(f, "beam"),
+        }
+    }
+}
+
+/// Node waiting to be decoded.
+pub(crate) struct Pending {
+    pub node: NodeId,
+    pub depth: usize,
+    pub score: f32,
+}
+
+impl PartialEq for Pending {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pending {}
+
+impl PartialOrd for Pending {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pending {
+    /// The best node is the greatest: the best scored, then the deepest, then the oldest.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then(self.depth.cmp(&other.depth))
+            .then(other.node.cmp(&self.node))
+    }
+}
+
+/// Nodes waiting to be decoded, in the order given by the strategy.
+pub(crate) enum Frontier {
+    Stack(Vec<Pending>),
+    Queue(VecDeque<Pending>),
+    Heap(BinaryHeap<Pending>),
+    Beam {
+        current: VecDeque<Pending>,
+        next: Vec<Pending>,
+        width: usize,
+        /// Whether some nodes were dropped to keep the width.
+        pruned: bool,
+    },
+}
+
+impl Frontier {
+    /// Create an empty frontier for the given strategy.
+    pub fn new(strategy: Strategy, beam_width: NonZeroUsize) -> Self {
+        match strategy {
+            Strategy::DepthFirst => Frontier::Stack(Vec::new()),
+            Strategy::BreadthFirst => Frontier::Queue(VecDeque::new()),
+            Strategy::BestFirst => Frontier::Heap(BinaryHeap::new()),
+            Strategy::Beam => Frontier::Beam {
+                current: VecDeque::new(),
+                next: Vec::new(),
+                width: beam_width.get(),
+                pruned: false,
+            },
+        }
+    }
+
+    /// Add the root node, it is decoded first whatever the strategy.
+    pub fn start(&mut self, root: Pending) {
+        match self {
+            Frontier::Beam { current, .. } => current.push_back(root),
+            frontier => frontier.extend(vec![root]),
+        }
+    }
+
+    /// Whether some nodes were dropped by the strategy, and will never be decoded.
+    pub fn pruned(&self) -> bool {
+        matches!(self, Frontier::Beam { pruned: true, .. })
+    }
+
+    /// Add the children of a decoded node, in the order they were found.
+    pub fn extend(&mut self, children: Vec<Pending>) {
+        match self {
+            // The first child must be on top of the stack.
+            Frontier::Stack(stack) => stack.extend(children.into_iter().rev()),
+            Frontier::Queue(queue) => queue.extend(children),
+            Frontier::Heap(heap) => heap.extend(children),
+            Frontier::Beam { next, .. } => next.extend(children),
+        }
+    }
+
+    /// Get the next node to decode.
+    pub fn pop(&mut self) -> Option<Pending> {
+        match self {
+            Frontier::Stack(stack) => stack.pop(),
+            Frontier::Queue(queue) => queue.pop_front(),
+            Frontier::Heap(heap) => heap.pop(),
+            Frontier::Beam {
+                current,
+                next,
+                width,
+                pruned,
+            } => {
+                // Move to the next depth, keeping only its best nodes.
+                if current.is_empty() {
+                    next.sort_by(|a, b| b.cmp(a));
+                    *pruned |= next.len() > *width;
+                    next.truncate(*width);
+                    current.extend(next.drain(..));
+                }
+                current.pop_front()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    fn pending(id: usize, depth: usize, score: f32) -> Pending {
+        Pending {
+            node: NodeId::new(id),
+            depth,
+            score,
+        }
+    }
+
+    fn pop_all(frontier: &mut Frontier) -> Vec<usize> {
+        std::iter::from_fn(|| frontier.pop())
+            .map(|pending| pending.node.index())
+            .collect()
+    }
+
+    #[test]
+    fn test_from_str() {
+        for strategy in [
+            Strategy::DepthFirst,
+            Strategy::BreadthFirst,
+            Strategy::BestFirst,
+            Strategy::Beam,
+        ] {
+            assert_eq!(strategy.to_string().parse::<Strategy>(), Ok(strategy));
+        }
+        assert_eq!(
+            "nope".parse::<Strategy>(),
+            Err(StrategyError::NotFound("nope".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_frontier_order() {
+        let children = || vec![pending(1, 1, 0.5), pending(2, 1, 0.9), pending(3, 1, 0.9)];
+
+        let mut frontier = Frontier::new(Strategy::DepthFirst, NonZeroUsize::MIN);
+        frontier.extend(children());
+        assert_eq!(pop_all(&mut frontier), vec![1, 2, 3]);
+
+        let mut frontier = Frontier::new(Strategy::BreadthFirst, NonZeroUsize::MIN);
+        frontier.extend(children());
+        assert_eq!(pop_all(&mut frontier), vec![1, 2, 3]);
+
+        let mut frontier = Frontier::new(Strategy::BestFirst, NonZeroUsize::MIN);
+        frontier.extend(children());
+        frontier.extend(vec![pending(4, 2, 0.9)]);
+        assert_eq!(pop_all(&mut frontier), vec![4, 2, 3, 1]);
+
+        let mut frontier = Frontier::new(Strategy::Beam, NonZeroUsize::new(2).unwrap());
+        frontier.extend(children());
+        assert!(!frontier.pruned());
+        assert_eq!(pop_all(&mut frontier), vec![2, 3]);
+        assert!(frontier.pruned());
+    }
+
+    #[test]
+    fn test_frontier_start() {
+        // The root node is decoded first, before its children are pruned.
+        let mut frontier = Frontier::new(Strategy::Beam, NonZeroUsize::MIN);
+        frontier.start(pending(0, 0, 0.0));
+        assert_eq!(pop_all(&mut frontier), vec![0]);
+        assert!(!frontier.pruned());
+
+        frontier.extend(vec![pending(1, 1, 0.5), pending(2, 1, 1.0)]);
+        assert_eq!(pop_all(&mut frontier), vec![2]);
+        assert!(frontier.pruned());
+    }
+}
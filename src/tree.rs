@@ -1,99 +1,477 @@
+//! Arena-based tree, its nodes are identified by a [`NodeId`].
+
 use std::fmt;
-use std::{cell::RefCell, rc::Rc};
+use std::ops::{Index, IndexMut};
 
-/// Reference to a node.
-pub type RefNode<T> = Rc<RefCell<Node<T>>>;
+/// Identifier of a node, stable for the lifetime of its tree.
+/// Nodes are numbered in the order they were added, the root node being 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeId(usize);
 
-/// Tree.
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl NodeId {
+    /// Create an identifier from the index of a node.
+    #[cfg(test)]
+    pub(crate) fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    /// Get the index of the node, the number of nodes added before it.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Node of a tree, linked to the others by their ids.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Node<T> {
+    data: T,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    depth: usize,
+}
+
+/// Tree, storing its nodes in an arena indexed by their ids.
+/// With the `serde` feature, the links between the nodes are checked on load.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Tree<T> {
-    root: RefNode<T>,
+    nodes: Vec<Node<T>>,
 }
 
 impl<T> Tree<T> {
     /// Create a new tree.
     pub fn new(seed: T) -> Self {
         Self {
-            root: Node::new(seed),
+            nodes: vec![Node {
+                data: seed,
+                parent: None,
+                children: Vec::new(),
+                depth: 0,
+            }],
         }
     }
 
     /// Get the root node.
-    pub fn root(&mut self) -> RefNode<T> {
-        self.root.clone()
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
     }
 
-    /// Get leaves of the tree.
-    pub fn leaves(&self) -> Vec<RefNode<T>> {
-        let mut leaves = Vec::new();
+    /// Get the number of nodes, including the root node.
+    pub fn size(&self) -> usize {
+        self.nodes.len()
+    }
 
-        // Ignore the root node.
-        for child in &self.root.borrow().children {
-            node_leaves(child, &mut leaves);
+    /// Add a child node, its id is the number of nodes added before it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parent node is not in the tree.
+    pub fn add_child(&mut self, parent: NodeId, data: T) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        let depth = self.nodes[parent.0].depth + 1;
+        self.nodes[parent.0].children.push(id);
+        self.nodes.push(Node {
+            data,
+            parent: Some(parent),
+            children: Vec::new(),
+            depth,
+        });
+        id
+    }
+
+    /// Get the data of a node, if it is in the tree.
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        self.nodes.get(id.0).map(|node| &node.data)
+    }
+
+    /// Get the parent of a node, `None` for the root node.
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id.0].parent
+    }
+
+    /// Get the children of a node, in the order they were added.
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        &self.nodes[id.0].children
+    }
+
+    /// Get the depth of a node, the root node being at depth 0.
+    pub fn depth(&self, id: NodeId) -> usize {
+        self.nodes[id.0].depth
+    }
+
+    /// Whether the node has no children.
+    pub fn is_leaf(&self, id: NodeId) -> bool {
+        self.nodes[id.0].children.is_empty()
+    }
+
+    /// Iterate from a node to the root node, both included.
+    pub fn path_to_root(&self, id: NodeId) -> PathToRoot<'_, T> {
+        PathToRoot {
+            tree: self,
+            next: Some(id),
+        }
+    }
+
+    /// Iterate over a subtree, each node before its children.
+    pub fn pre_order(&self, id: NodeId) -> PreOrder<'_, T> {
+        PreOrder {
+            tree: self,
+            stack: vec![id],
+        }
+    }
+
+    /// Iterate over a subtree, each node after its children.
+    pub fn post_order(&self, id: NodeId) -> PostOrder<'_, T> {
+        PostOrder {
+            tree: self,
+            stack: vec![(id, false)],
         }
-        leaves
+    }
+
+    /// Iterate over a subtree, each node before its children like [`Tree::pre_order`].
+    /// The children rejected by the filter are skipped along with their subtree,
+    /// and each visit tells which of its nodes are the last visited children, e.g. to draw the tree.
+    pub fn walk<F>(&self, id: NodeId, filter: F) -> Walk<'_, T, F>
+    where
+        F: FnMut(NodeId) -> bool,
+    {
+        Walk {
+            tree: self,
+            filter,
+            stack: vec![Visit { id, last: vec![] }],
+        }
+    }
+
+    /// Get leaves of the tree, in pre-order.
+    /// The root node is ignored, a tree without children has no leaves.
+    pub fn leaves(&self) -> Vec<NodeId> {
+        self.pre_order(self.root())
+            .skip(1)
+            .filter(|id| self.is_leaf(*id))
+            .collect()
     }
 }
 
-/// Node.
-#[derive(Clone, PartialEq, Eq)]
-pub struct Node<T> {
-    pub children: Vec<RefNode<T>>,
-    pub parent: Option<RefNode<T>>,
-    pub data: Rc<T>,
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Tree<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// The nodes as serialized, before their links are checked.
+        #[derive(serde::Deserialize)]
+        struct Nodes<T> {
+            nodes: Vec<Node<T>>,
+        }
+
+        let Nodes { nodes } = Nodes::deserialize(deserializer)?;
+        check_links(&nodes).map_err(serde::de::Error::custom)?;
+        Ok(Self { nodes })
+    }
 }
 
-impl<T> fmt::Debug for Node<T>
-where
-    T: fmt::Debug,
-{
-    /// Remove the parent field from the debug output and avoid infinite recursion.
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Node")
-            // Remove `RefCell` from the debug output.
-            .field(
-                "children",
-                &self.children.iter().map(|c| c.borrow()).collect::<Vec<_>>(),
-            )
-            .field("data", &self.data)
-            .finish()
+/// Check that the nodes form a tree, as built by [`Tree::add_child`].
+/// Each node but the root node is a child of an earlier node, listed once by its parent, so there
+/// is no cycle.
+#[cfg(feature = "serde")]
+fn check_links<T>(nodes: &[Node<T>]) -> Result<(), String> {
+    let root = nodes.first().ok_or("the tree has no root node")?;
+    if root.parent.is_some() || root.depth != 0 {
+        return Err("the root node has a parent or a depth".to_string());
+    }
+
+    let mut listed = vec![false; nodes.len()];
+    for (index, node) in nodes.iter().enumerate() {
+        if let Some(parent) = node.parent {
+            if parent.0 >= index {
+                return Err(format!(
+                    "node {} is not the child of an earlier node",
+                    index
+                ));
+            }
+            if node.depth != nodes[parent.0].depth + 1 {
+                return Err(format!("node {} has an invalid depth", index));
+            }
+        } else if index != 0 {
+            return Err(format!("node {} has no parent", index));
+        }
+
+        for &child in &node.children {
+            if nodes.get(child.0).and_then(|child| child.parent) != Some(NodeId(index)) {
+                return Err(format!("node {} is not a child of node {}", child, index));
+            }
+            if std::mem::replace(&mut listed[child.0], true) {
+                return Err(format!("node {} is listed twice", child));
+            }
+        }
+    }
+    match listed.iter().skip(1).position(|&listed| !listed) {
+        Some(index) => Err(format!("node {} is not listed by its parent", index + 1)),
+        None => Ok(()),
     }
 }
 
-impl<T> Node<T> {
-    /// Create a new node.
-    pub fn new(data: T) -> RefNode<T> {
-        Rc::new(RefCell::new(Self {
-            children: Vec::new(),
-            parent: None,
-            data: Rc::new(data),
-        }))
+impl<T> Index<NodeId> for Tree<T> {
+    type Output = T;
+
+    fn index(&self, id: NodeId) -> &T {
+        &self.nodes[id.0].data
     }
+}
 
-    /// Create a new node with parent.
-    pub fn new_with_parent(data: T, parent: RefNode<T>) -> RefNode<T> {
-        Rc::new(RefCell::new(Self {
-            children: Vec::new(),
-            parent: Some(parent),
-            data: Rc::new(data),
-        }))
+impl<T> IndexMut<NodeId> for Tree<T> {
+    fn index_mut(&mut self, id: NodeId) -> &mut T {
+        &mut self.nodes[id.0].data
+    }
+}
+
+/// Iterator from a node to the root node, see [`Tree::path_to_root`].
+pub struct PathToRoot<'a, T> {
+    tree: &'a Tree<T>,
+    next: Option<NodeId>,
+}
+
+impl<T> Iterator for PathToRoot<'_, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let id = self.next?;
+        self.next = self.tree.parent(id);
+        Some(id)
+    }
+}
+
+/// Pre-order iterator over a subtree, see [`Tree::pre_order`].
+pub struct PreOrder<'a, T> {
+    tree: &'a Tree<T>,
+    stack: Vec<NodeId>,
+}
+
+impl<T> Iterator for PreOrder<'_, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let id = self.stack.pop()?;
+        // The first child must be on top of the stack.
+        self.stack
+            .extend(self.tree.children(id).iter().rev().copied());
+        Some(id)
     }
 }
 
-/// Add a child node.
-pub fn add_child<T>(node: &RefNode<T>, data: T) -> RefNode<T> {
-    let child = Node::new_with_parent(data, Rc::clone(node));
-    node.borrow_mut().children.push(child.clone());
-    child
+/// Post-order iterator over a subtree, see [`Tree::post_order`].
+pub struct PostOrder<'a, T> {
+    tree: &'a Tree<T>,
+    /// Nodes to visit, and whether their children were already pushed.
+    stack: Vec<(NodeId, bool)>,
 }
 
-/// Get leaves of the tree.
-fn node_leaves<T>(node: &RefNode<T>, leaves: &mut Vec<RefNode<T>>) {
-    if node.borrow().children.is_empty() {
-        leaves.push(Rc::clone(node));
-    } else {
-        for child in &node.borrow().children {
-            node_leaves(child, leaves);
+impl<T> Iterator for PostOrder<'_, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        loop {
+            let (id, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(id);
+            }
+            self.stack.push((id, true));
+            self.stack
+                .extend(self.tree.children(id).iter().rev().map(|&c| (c, false)));
         }
     }
 }
+
+/// Node visited by [`Tree::walk`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Visit {
+    /// The visited node.
+    pub id: NodeId,
+    /// For each node of the path from the starting node, excluded, to the visited node, included,
+    /// whether it is the last visited child of its parent.
+    pub last: Vec<bool>,
+}
+
+impl Visit {
+    /// Get the depth of the node, relative to the starting node.
+    pub fn depth(&self) -> usize {
+        self.last.len()
+    }
+}
+
+/// Filtered pre-order iterator over a subtree, see [`Tree::walk`].
+pub struct Walk<'a, T, F> {
+    tree: &'a Tree<T>,
+    filter: F,
+    stack: Vec<Visit>,
+}
+
+impl<T, F> Iterator for Walk<'_, T, F>
+where
+    F: FnMut(NodeId) -> bool,
+{
+    type Item = Visit;
+
+    fn next(&mut self) -> Option<Visit> {
+        let visit = self.stack.pop()?;
+        let children = self
+            .tree
+            .children(visit.id)
+            .iter()
+            .copied()
+            .filter(|&child| (self.filter)(child))
+            .collect::<Vec<_>>();
+
+        // The first child must be on top of the stack.
+        let count = children.len();
+        self.stack
+            .extend(children.into_iter().enumerate().rev().map(|(i, child)| {
+                let mut last = visit.last.clone();
+                last.push(i + 1 == count);
+                Visit { id: child, last }
+            }));
+        Some(visit)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(tarpaulin_include))]
+mod tests {
+    use super::*;
+
+    /// Build the tree:
+    /// ```text
+    /// a
+    /// ├── b
+    /// │   ├── d
+    /// │   └── e
+    /// └── c
+    /// ```
+    fn sample() -> (Tree<char>, Vec<NodeId>) {
+        let mut tree = Tree::new('a');
+        let a = tree.root();
+        let b = tree.add_child(a, 'b');
+        let c = tree.add_child(a, 'c');
+        let d = tree.add_child(b, 'd');
+        let e = tree.add_child(b, 'e');
+        (tree, vec![a, b, c, d, e])
+    }
+
+    fn data(tree: &Tree<char>, ids: impl Iterator<Item = NodeId>) -> String {
+        ids.map(|id| tree[id]).collect()
+    }
+
+    #[test]
+    fn test_accessors() {
+        let (tree, ids) = sample();
+        let [a, b, c, d, e] = ids[..] else {
+            unreachable!()
+        };
+
+        assert_eq!(tree.size(), 5);
+        assert_eq!(e.index(), 4);
+        assert_eq!(tree.get(d), Some(&'d'));
+        assert_eq!(tree.get(NodeId::new(5)), None);
+        assert_eq!(tree.parent(a), None);
+        assert_eq!(tree.parent(d), Some(b));
+        assert_eq!(tree.children(a), &[b, c]);
+        assert_eq!(tree.depth(a), 0);
+        assert_eq!(tree.depth(e), 2);
+        assert!(tree.is_leaf(c));
+        assert!(!tree.is_leaf(b));
+    }
+
+    #[test]
+    fn test_traversals() {
+        let (tree, ids) = sample();
+        let root = tree.root();
+
+        assert_eq!(data(&tree, tree.path_to_root(ids[4])), "eba");
+        assert_eq!(data(&tree, tree.pre_order(root)), "abdec");
+        assert_eq!(data(&tree, tree.post_order(root)), "debca");
+        assert_eq!(data(&tree, tree.pre_order(ids[1])), "bde");
+        assert_eq!(data(&tree, tree.leaves().into_iter()), "dec");
+        assert!(Tree::new('a').leaves().is_empty());
+    }
+
+    #[test]
+    fn test_walk() {
+        let (tree, ids) = sample();
+        let visits = tree
+            .walk(tree.root(), |id| tree[id] != 'e')
+            .map(|visit| (tree[visit.id], visit.last))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            visits,
+            vec![
+                ('a', vec![]),
+                ('b', vec![false]),
+                ('d', vec![false, true]),
+                ('c', vec![true]),
+            ]
+        );
+        assert_eq!(
+            tree.walk(ids[1], |_| true).map(|v| v.depth()).max(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_deserialize() {
+        let (tree, _) = sample();
+        let json = serde_json::to_string(&tree).unwrap();
+        let loaded: Tree<char> = serde_json::from_str(&json).unwrap();
+        assert_eq!(data(&loaded, loaded.pre_order(loaded.root())), "abdec");
+
+        let load =
+            |nodes: &str| serde_json::from_str::<Tree<char>>(&format!(r#"{{"nodes": {}}}"#, nodes));
+        let node = |parent: &str, children: &str, depth: usize| {
+            format!(
+                r#"{{"data": "x", "parent": {}, "children": {}, "depth": {}}}"#,
+                parent, children, depth
+            )
+        };
+
+        // Empty tree, unknown child, cycle, node listed twice or not listed.
+        assert!(load("[]").is_err());
+        assert!(load(&format!("[{}]", node("null", "[5]", 0))).is_err());
+        assert!(load(&format!(
+            "[{}, {}, {}]",
+            node("null", "[]", 0),
+            node("2", "[2]", 1),
+            node("1", "[1]", 2)
+        ))
+        .is_err());
+        assert!(load(&format!(
+            "[{}, {}]",
+            node("null", "[1, 1]", 0),
+            node("0", "[]", 1)
+        ))
+        .is_err());
+        assert!(load(&format!(
+            "[{}, {}]",
+            node("null", "[]", 0),
+            node("0", "[]", 1)
+        ))
+        .is_err());
+        assert!(load(&format!(
+            "[{}, {}]",
+            node("null", "[1]", 0),
+            node("0", "[]", 3)
+        ))
+        .is_err());
+        assert!(load(&format!(
+            "[{}, {}]",
+            node("null", "[1]", 0),
+            node("0", "[]", 1)
+        ))
+        .is_ok());
+    }
+}